@@ -14,6 +14,46 @@ pub fn calculate_velocity(
     velocity + acceleration * time
 }
 
+/// `steps` titik `(x, y)` lintasan parabola dari titik asal
+/// dengan jarak waktu `dt`, y positif ke atas. `angle_deg`
+/// diukur dari lantai
+pub fn projectile_points(
+    v0: f64,
+    angle_deg: f64,
+    gravity: f64,
+    steps: usize,
+    dt: f64,
+) -> Vec<(f64, f64)> {
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+
+    (0..steps)
+        .map(|i| {
+            let time = i as f64 * dt;
+            (
+                calculate_distance(v0 * cos, 0.0, time),
+                calculate_distance(
+                    v0 * sin,
+                    -gravity,
+                    time,
+                ),
+            )
+        })
+        .collect()
+}
+
+/// tinggi parabola pembatas (safety parabola) di jarak
+/// horizontal `x` dari titik lempar: titik tertinggi yang
+/// bisa dicapai lemparan berkecepatan `v0` dengan sudut
+/// berapapun
+pub fn envelope_height(
+    v0: f64,
+    gravity: f64,
+    x: f64,
+) -> f64 {
+    v0 * v0 / (2.0 * gravity)
+        - gravity * x * x / (2.0 * v0 * v0)
+}
+
 pub fn mid_point(
     x_center: usize,
     y_center: usize,
@@ -73,3 +113,46 @@ pub fn mid_point(
 
     points
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projectile_starts_at_the_origin_and_peaks_at_v2_sin2_over_2g(
+    ) {
+        let (v0, angle, gravity) = (400.0, 30.0, 800.0);
+        // titik tertinggi tepat di t = 0.25
+        let points =
+            projectile_points(v0, angle, gravity, 60, 0.01);
+
+        assert_eq!(points.len(), 60);
+        assert_eq!(points[0], (0.0, 0.0));
+
+        let apex = points
+            .iter()
+            .map(|it| it.1)
+            .fold(f64::MIN, f64::max);
+        let sin = f64::to_radians(angle).sin();
+        let expected =
+            v0 * v0 * sin * sin / (2.0 * gravity);
+        assert!((apex - expected).abs() < 1e-9, "{}", apex);
+    }
+
+    #[test]
+    fn envelope_peaks_at_v2_over_2g_above_the_origin() {
+        let (v0, gravity) = (400.0, 800.0);
+        let apex = v0 * v0 / (2.0 * gravity);
+
+        assert_eq!(envelope_height(v0, gravity, 0.0), apex);
+        for x in [-300.0, -10.0, 10.0, 300.0] {
+            assert!(envelope_height(v0, gravity, x) < apex);
+        }
+        // jangkauan terjauh v²/g ada di tanah
+        let range = v0 * v0 / gravity;
+        assert!(
+            envelope_height(v0, gravity, range).abs()
+                < 1e-9
+        );
+    }
+}
@@ -1,21 +1,57 @@
 use std::{ops::RangeInclusive, time::Duration};
 
-use crate::{calculate_distance, calculate_velocity, Now};
-use serde::{Deserialize, Serialize};
+use crate::{
+    calculate_distance, calculate_velocity, WallBehavior,
+};
 
-#[derive(Default, Serialize, Deserialize)]
+#[derive(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct HorizontalState {
     play: Option<i8>,
-    #[serde(skip)]
-    pub start: Now,
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub duration: Duration,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    traveled: f64,
+    /// durasi gerakan saat bola terakhir dimainkan
+    #[cfg_attr(feature = "serde", serde(skip))]
+    total_duration: Duration,
+    /// gerak lurus beraturan, bola tidak melambat dan tidak
+    /// pernah berhenti sendiri
+    #[cfg_attr(feature = "serde", serde(default))]
+    constant: bool,
+    /// perilaku bola di dinding kiri
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub left: WallBehavior,
+    /// perilaku bola di dinding kanan
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub right: WallBehavior,
 
     pub velocity: f64,
     pub acceleration: f64,
+    /// kecepatan awal yang diisi pengguna, `velocity` berkurang
+    /// selama bergerak sedangkan ini tidak
+    #[cfg_attr(feature = "serde", serde(default))]
+    launch_velocity: f64,
 }
 
 impl HorizontalState {
+    /// state diam dengan kecepatan dan perlambatan awal
+    /// tertentu
+    pub fn with_params(
+        velocity: f64,
+        acceleration: f64,
+    ) -> Self {
+        Self {
+            velocity,
+            acceleration,
+            launch_velocity: velocity,
+            ..Default::default()
+        }
+    }
+
     pub fn is_play(&self) -> bool {
         self.play.is_some()
     }
@@ -23,26 +59,237 @@ impl HorizontalState {
         self.play = None;
     }
 
+    /// ubah kecepatan, jika sedang bergerak durasi dihitung
+    /// ulang dari kecepatan yang baru. saat diam juga menjadi
+    /// kecepatan awal peluncuran berikutnya
+    pub fn set_velocity(&mut self, velocity: f64) {
+        self.velocity = velocity.max(0.0);
+        if !self.is_play() {
+            self.launch_velocity = self.velocity;
+        }
+        self.restart();
+    }
+
+    /// kecepatan awal setiap [`Self::play_left`] dan
+    /// [`Self::play_right`], tidak ikut berkurang selama
+    /// bergerak
+    pub fn launch_velocity(&self) -> f64 {
+        // state lama belum menyimpan kecepatan awal
+        if self.launch_velocity > 0.0 {
+            self.launch_velocity
+        } else {
+            self.velocity
+        }
+    }
+
+    /// isi kecepatan awal dari `velocity` untuk simpanan
+    /// lama yang belum menyimpannya
+    pub(crate) fn restore_launch_velocity(&mut self) {
+        if self.launch_velocity <= 0.0 {
+            self.launch_velocity = self.velocity;
+        }
+    }
+
+    /// ubah percepatan, jika sedang bergerak durasi dihitung
+    /// ulang dari percepatan yang baru
+    pub fn set_acceleration(&mut self, acceleration: f64) {
+        self.acceleration = acceleration.max(0.0);
+        self.restart();
+    }
+
+    /// sisa waktu sampai bola berhenti
+    pub fn time_remaining(&self) -> Duration {
+        if self.is_play() {
+            // durasi dihitung ulang setiap langkah
+            self.duration
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// kecepatan bola searah sumbu x, nol jika diam
+    pub fn velocity_x(&self) -> f64 {
+        self.play.map_or(0.0, |direction| {
+            self.velocity * direction as f64
+        })
+    }
+
+    /// percepatan bola searah sumbu x, nol jika diam
+    pub fn acceleration_x(&self) -> f64 {
+        self.play.map_or(0.0, |direction| {
+            -self.deceleration() * direction as f64
+        })
+    }
+
+    /// perkiraan waktu sampai bola di `current_x` mencapai
+    /// `target_x`, tanpa memperhitungkan pantulan dinding.
+    ///
+    /// `None` jika bola diam, bergerak menjauh, berhenti
+    /// sebelum sampai atau waktunya terlalu lama untuk
+    /// [`Duration`]
+    pub fn time_to_reach(
+        &self,
+        current_x: f64,
+        target_x: f64,
+    ) -> Option<Duration> {
+        let direction = self.play? as f64;
+        let distance = (target_x - current_x) * direction;
+        if !distance.is_finite() || distance < 0.0 {
+            return None;
+        }
+
+        let velocity = self.velocity;
+        let deceleration = self.deceleration();
+
+        // x = v*t - a*t²/2
+        let time = if deceleration == 0.0 {
+            if velocity <= 0.0 {
+                return (distance == 0.0)
+                    .then_some(Duration::ZERO);
+            }
+            distance / velocity
+        } else {
+            let discriminant = velocity * velocity
+                - 2.0 * deceleration * distance;
+            if discriminant < 0.0 {
+                return None;
+            }
+            (velocity - discriminant.sqrt()) / deceleration
+        };
+
+        Duration::try_from_secs_f64(time.max(0.0)).ok()
+    }
+
+    /// balik arah gerak dan kalikan kecepatan dengan
+    /// `restitution`
+    pub(crate) fn bounce(&mut self, restitution: f64) {
+        if let Some(direction) = self.play {
+            self.velocity *= restitution;
+            self.play(-direction);
+        }
+    }
+
+    /// seberapa jauh gerakan sejak dimainkan atau terakhir
+    /// diubah kecepatan dan perlambatannya sudah berjalan,
+    /// dari 0 sampai 1. selalu 0 jika diam atau bergerak
+    /// dengan kecepatan tetap
+    pub fn progress(&self) -> f32 {
+        if !self.is_play()
+            || self.constant
+            || self.total_duration.is_zero()
+        {
+            return 0.0;
+        }
+
+        let remaining = self.time_remaining().as_secs_f64();
+        let total = self.total_duration.as_secs_f64();
+        (1.0 - remaining / total).clamp(0.0, 1.0) as f32
+    }
+
+    /// hitung ulang durasi dari kecepatan sekarang, sisa
+    /// gerakan menjadi acuan [`Self::progress`] yang baru
+    fn restart(&mut self) {
+        if let Some(direction) = self.play {
+            self.play(direction);
+            self.total_duration = self.duration;
+        }
+    }
+
     pub fn play_left(&mut self) {
+        self.velocity = self.launch_velocity();
+        self.traveled = 0.0;
+        self.constant = false;
         self.play(-1);
+        self.total_duration = self.duration;
     }
 
     pub fn play_right(&mut self) {
+        self.velocity = self.launch_velocity();
+        self.traveled = 0.0;
+        self.constant = false;
         self.play(1);
+        self.total_duration = self.duration;
+    }
+
+    /// gerakkan bola dengan kecepatan tetap (GLB) ke arah
+    /// `direction`, bola baru berhenti saat [`Self::stop`]
+    pub fn play_constant(
+        &mut self,
+        velocity: f64,
+        direction: i8,
+    ) {
+        self.traveled = 0.0;
+        self.constant = true;
+        self.velocity = velocity.abs();
+        self.play(if direction < 0 { -1 } else { 1 });
+        self.total_duration = self.duration;
+    }
+
+    /// gerakkan bola dengan kecepatan `velocity_x`, negatif
+    /// berarti ke kiri. bola berhenti jika nol.
+    ///
+    /// bola yang sedang bergerak tetap dengan jenis gerak dan
+    /// jarak tempuhnya, sedangkan bola diam mulai gerakan baru
+    /// yang melambat. [`Self::progress`] dihitung dari sini
+    pub fn set_velocity_x(&mut self, velocity_x: f64) {
+        if velocity_x == 0.0 {
+            self.stop();
+            return;
+        }
+
+        if !self.is_play() {
+            self.traveled = 0.0;
+            self.constant = false;
+        }
+        self.velocity = velocity_x.abs();
+        self.play(if velocity_x < 0.0 { -1 } else { 1 });
+        self.total_duration = self.duration;
+    }
+
+    /// cek apakah bola bergerak dengan kecepatan tetap
+    pub fn is_constant(&self) -> bool {
+        self.constant
+    }
+
+    /// ubah jenis gerak tanpa mengubah kecepatan, berlaku
+    /// mulai [`Self::set_velocity_x`] atau peluncuran
+    /// berikutnya
+    pub(crate) fn set_constant(&mut self, constant: bool) {
+        self.constant = constant;
+    }
+
+    /// total jarak yang sudah ditempuh sejak bola terakhir
+    /// dimainkan, termasuk setelah memantul di dinding
+    pub fn distance_traveled(&self) -> f64 {
+        self.traveled
     }
 
     fn play(&mut self, direction: i8) {
-        self.start.reset();
         self.play = Some(direction);
-        let duration =
-            self.velocity.abs() / self.acceleration.abs();
-        self.duration = Duration::from_secs_f64(duration);
+        self.duration = if self.constant {
+            Duration::MAX
+        } else {
+            let duration = self.velocity.abs()
+                / self.acceleration.abs();
+            Duration::try_from_secs_f64(duration)
+                .unwrap_or(Duration::MAX)
+        };
+    }
+
+    /// perlambatan bola, nol jika bergerak dengan kecepatan
+    /// tetap
+    fn deceleration(&self) -> f64 {
+        if self.constant {
+            0.0
+        } else {
+            self.acceleration
+        }
     }
 
     pub fn distance_at(&self, time: f64) -> f64 {
         calculate_distance(
             self.velocity,
-            -self.acceleration,
+            -self.deceleration(),
             time,
         )
     }
@@ -50,43 +297,460 @@ impl HorizontalState {
     pub fn velocity_at(&self, time: f64) -> f64 {
         calculate_velocity(
             self.velocity,
-            -self.acceleration,
+            -self.deceleration(),
             time,
         )
     }
 
-    pub fn mv(
+    /// gerakkan bola sejauh `dt`.
+    ///
+    /// mengembalikan `true` jika bola berhenti di dinding
+    /// [`WallBehavior::Stop`], pemilik state sebaiknya ikut
+    /// menghentikan gerakan vertikal
+    pub fn step(
         &mut self,
         pos: &mut f32,
         range: RangeInclusive<f32>,
-    ) {
+        dt: Duration,
+    ) -> bool {
         if let Some(mut direction) = self.play {
-            let time = self
-                .start
-                .elapsed()
-                .min(self.duration)
-                .as_secs_f64();
-            self.velocity = self.velocity_at(time);
+            let time = dt.min(self.duration).as_secs_f64();
+            // jarak dihitung dari kecepatan di awal langkah
             let mut distance = self.distance_at(time);
+            self.velocity = self.velocity_at(time);
 
+            let mut hit_wall = None;
             while distance > 0.0 {
                 let move_by = distance.min(5.0);
                 distance -= move_by;
+                self.traveled += move_by;
                 *pos +=
                     (move_by as f32) * (direction as f32);
 
-                if !range.contains(pos) {
-                    direction *= -1;
+                if range.contains(pos) {
+                    continue;
+                }
+
+                let width = range.end() - range.start();
+                let past_end = *pos > *range.end();
+                let behavior = if past_end {
+                    self.right
+                } else {
+                    self.left
+                };
+                match behavior {
+                    WallBehavior::Wrap if width > 0.0 => {
+                        if past_end {
+                            *pos -= width;
+                        } else {
+                            *pos += width;
+                        }
+                    }
+                    WallBehavior::Bounce => {
+                        // bagian yang melewati dinding
+                        // dipantulkan kembali
+                        let wall = if past_end {
+                            *range.end()
+                        } else {
+                            *range.start()
+                        };
+                        *pos = 2.0 * wall - *pos;
+                        direction *= -1;
+                    }
+                    // tidak ada sisi seberang untuk muncul
+                    // kembali, tahan di dinding
+                    WallBehavior::Clamp
+                    | WallBehavior::Wrap
+                    | WallBehavior::Stop => {
+                        *pos = pos.clamp(
+                            *range.start(),
+                            *range.end(),
+                        );
+                        hit_wall = Some(behavior);
+                        break;
+                    }
                 }
             }
 
+            if let Some(behavior) = hit_wall {
+                self.stop();
+                return behavior == WallBehavior::Stop;
+            }
+
             self.play = Some(direction);
 
-            if self.start.elapsed() < self.duration {
+            if dt < self.duration {
                 self.play(direction);
             } else {
                 self.stop();
             }
         }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Now;
+
+    const DT: Duration = Duration::from_millis(20);
+
+    /// jalankan `state` selama `duration` dengan langkah
+    /// [`DT`], waktu dicatat di `clock`
+    fn run_for(
+        state: &mut HorizontalState,
+        pos: &mut f32,
+        range: RangeInclusive<f32>,
+        clock: &mut Now,
+        duration: Duration,
+    ) {
+        let end = clock.elapsed() + duration;
+        while clock.elapsed() < end {
+            clock.advance(DT);
+            state.step(pos, range.clone(), DT);
+        }
+    }
+
+    #[test]
+    fn play_right_moves_right_and_slows_down() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        state.play_right();
+
+        assert!(state.is_play());
+        assert_eq!(state.velocity_x(), 400.0);
+        assert_eq!(state.acceleration_x(), -100.0);
+    }
+
+    #[test]
+    fn with_params_then_play_right_sets_duration() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        assert!(!state.is_play());
+
+        state.play_right();
+
+        assert_eq!(state.duration, Duration::from_secs(4));
+        assert_eq!(
+            state.total_duration,
+            Duration::from_secs(4)
+        );
+    }
+
+    #[test]
+    fn mid_motion_position_matches_calculate_distance() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let mut clock = Now::manual();
+        let mut pos = 0.0;
+        state.play_right();
+
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            Duration::from_secs(1),
+        );
+
+        let time = clock.elapsed_secs();
+        let expected =
+            calculate_distance(400.0, -100.0, time);
+        assert!(
+            (pos as f64 - expected).abs() < 1e-2,
+            "{} != {}",
+            pos,
+            expected
+        );
+        assert!(
+            (state.velocity_x()
+                - calculate_velocity(400.0, -100.0, time))
+            .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn stops_after_duration() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let mut clock = Now::manual();
+        let mut pos = 0.0;
+        state.play_right();
+        let duration = state.time_remaining();
+
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            duration + DT,
+        );
+
+        assert!(!state.is_play());
+        assert_eq!(state.velocity_x(), 0.0);
+        // v²/2a
+        assert!((pos - 800.0).abs() < 1e-2, "{}", pos);
+
+        let stopped = pos;
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            Duration::from_secs(1),
+        );
+        assert_eq!(pos, stopped);
+    }
+
+    #[test]
+    fn playing_again_after_stopping_repeats_the_motion() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let play_once = |state: &mut HorizontalState| {
+            let mut pos = 0.0;
+            let mut path = vec![];
+            state.play_right();
+            while state.is_play() {
+                state.step(&mut pos, 0.0..=10_000.0, DT);
+                path.push(pos);
+            }
+            path
+        };
+
+        let first = play_once(&mut state);
+        assert_eq!(state.velocity_x(), 0.0);
+        assert_eq!(state.launch_velocity(), 400.0);
+        let second = play_once(&mut state);
+
+        assert!(first.len() > 1);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn bounces_back_and_forth_inside_the_range() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let mut clock = Now::manual();
+        let mut pos = 50.0;
+        state.play_right();
+
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=100.0,
+            &mut clock,
+            Duration::from_millis(500),
+        );
+
+        // 187.5 ke kanan: 50 sampai dinding kanan, 100
+        // sampai dinding kiri, lalu 37.5 lagi ke kanan
+        assert!((pos - 37.5).abs() < 1e-2, "{}", pos);
+        assert!(state.velocity_x() > 0.0);
+        assert!(
+            (state.distance_traveled() - 187.5).abs()
+                < 1e-2
+        );
+    }
+
+    #[test]
+    fn direction_persists_across_steps() {
+        let mut state =
+            HorizontalState::with_params(300.0, 100.0);
+        let mut pos = 420.0;
+        state.play_left();
+
+        let mut last = pos;
+        for _ in 0..100 {
+            state.step(&mut pos, 0.0..=1000.0, DT);
+            assert!(state.velocity_x() < 0.0);
+            assert!(pos < last, "{} >= {}", pos, last);
+            last = pos;
+        }
+
+        // 400 ke kiri, sisa 20 dari dinding. setelah memantul
+        // di kiri arah tetap ke kanan
+        state.step(
+            &mut pos,
+            0.0..=1000.0,
+            Duration::from_millis(400),
+        );
+        for _ in 0..5 {
+            assert!(state.velocity_x() > 0.0);
+            state.step(&mut pos, 0.0..=1000.0, DT);
+        }
+    }
+
+    #[test]
+    fn set_acceleration_while_playing_updates_time_remaining(
+    ) {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        state.play_right();
+        assert_eq!(
+            state.time_remaining(),
+            Duration::from_secs(4)
+        );
+
+        state.set_acceleration(200.0);
+
+        assert_eq!(
+            state.time_remaining(),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn constant_motion_covers_v_t_without_slowing() {
+        let mut state =
+            HorizontalState::with_params(0.0, 100.0);
+        state.play_constant(200.0, 1);
+        let mut pos = 0.0;
+
+        for _ in 0..50 {
+            state.step(
+                &mut pos,
+                0.0..=10_000.0,
+                Duration::from_millis(20),
+            );
+        }
+
+        assert!((pos - 200.0).abs() < 1e-3, "{}", pos);
+        assert_eq!(state.velocity_x(), 200.0);
+        assert!(state.is_play());
+    }
+
+    #[test]
+    fn time_to_reach_reachable_and_unreachable_targets() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        assert_eq!(state.time_to_reach(0.0, 100.0), None);
+
+        state.play_right();
+        // 400t - 50t² = 600 saat t = 2
+        let time = state.time_to_reach(0.0, 600.0).unwrap();
+        assert!(
+            (time.as_secs_f64() - 2.0).abs() < 1e-9,
+            "{:?}",
+            time
+        );
+        assert_eq!(
+            state.time_to_reach(50.0, 50.0),
+            Some(Duration::ZERO)
+        );
+
+        // berhenti di 800
+        assert_eq!(state.time_to_reach(0.0, 801.0), None);
+        // di belakang bola
+        assert_eq!(state.time_to_reach(100.0, 20.0), None);
+
+        state.play_left();
+        let time = state.time_to_reach(600.0, 0.0).unwrap();
+        assert!((time.as_secs_f64() - 2.0).abs() < 1e-9);
+        assert_eq!(
+            state.time_to_reach(600.0, f64::NAN),
+            None
+        );
+
+        // terlalu lambat, waktunya melebihi Duration::MAX
+        state.play_constant(1e-20, 1);
+        assert_eq!(state.time_to_reach(0.0, 1.0), None);
+    }
+
+    #[test]
+    fn progress_is_half_way_at_the_midpoint() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let mut clock = Now::manual();
+        let mut pos = 0.0;
+        assert_eq!(state.progress(), 0.0);
+
+        state.play_right();
+        assert_eq!(state.progress(), 0.0);
+        let half = state.time_remaining() / 2;
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            half,
+        );
+
+        assert_eq!(clock.elapsed(), half);
+        let progress = state.progress();
+        assert!(
+            (progress - 0.5).abs() < 1e-3,
+            "{}",
+            progress
+        );
+    }
+
+    #[test]
+    fn progress_follows_a_mid_motion_acceleration_change() {
+        let mut state =
+            HorizontalState::with_params(400.0, 100.0);
+        let mut clock = Now::manual();
+        let mut pos = 0.0;
+        state.play_right();
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            Duration::from_secs(1),
+        );
+        assert!((state.progress() - 0.25).abs() < 1e-3);
+
+        // sisa 300 / 300 = 1 detik, dihitung dari sini
+        state.set_acceleration(300.0);
+        assert_eq!(state.progress(), 0.0);
+        run_for(
+            &mut state,
+            &mut pos,
+            0.0..=10_000.0,
+            &mut clock,
+            Duration::from_millis(500),
+        );
+
+        let progress = state.progress();
+        assert!(
+            (progress - 0.5).abs() < 1e-3,
+            "{}",
+            progress
+        );
+    }
+
+    #[test]
+    fn clamp_and_stop_differ_at_the_right_wall() {
+        let at_right_wall = |behavior, range| {
+            let mut state =
+                HorizontalState::with_params(400.0, 100.0);
+            state.right = behavior;
+            state.play_right();
+            let mut pos = 95.0;
+            let stopped = state.step(&mut pos, range, DT);
+            (pos, state.is_play(), stopped)
+        };
+
+        assert_eq!(
+            at_right_wall(WallBehavior::Clamp, 0.0..=100.0),
+            (100.0, false, false)
+        );
+        assert_eq!(
+            at_right_wall(WallBehavior::Stop, 0.0..=100.0),
+            (100.0, false, true)
+        );
+        // tanpa lebar tidak ada sisi seberang, tertahan
+        // seperti clamp dan tidak memantul
+        assert_eq!(
+            at_right_wall(WallBehavior::Wrap, 95.0..=95.0),
+            (95.0, false, false)
+        );
+        let (pos, play, stopped) = at_right_wall(
+            WallBehavior::Bounce,
+            0.0..=100.0,
+        );
+        assert!(pos < 100.0 && play && !stopped);
     }
 }
@@ -1,6 +1,8 @@
 mod formula;
 mod horizontal_state;
 mod now;
+pub mod playback;
+pub mod replay;
 pub mod slider;
 mod state;
 mod vertical_state;
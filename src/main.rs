@@ -12,17 +12,15 @@ impl App {
         Self {
             glbb: it
                 .storage
-                .map(|storage| {
-                    storage
-                        .get_string("glbb")
-                        .map(|it| ron::from_str(&it).ok())
+                .and_then(|storage| {
+                    storage.get_string("glbb")
                 })
-                .flatten()
-                .flatten()
-                .unwrap_or(GLBBState {
-                    original_radius: 30.0,
-                    ..Default::default()
-                }),
+                .and_then(|it| ron::from_str(&it).ok())
+                .map(|mut it: GLBBState| {
+                    it.migrate();
+                    it
+                })
+                .unwrap_or_default(),
             size: egui::Vec2::ZERO,
         }
     }
@@ -30,6 +28,10 @@ impl App {
 
 impl eframe::App for App {
     fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        if !self.glbb.take_dirty() {
+            return;
+        }
+
         _storage.set_string(
             "glbb",
             ron::to_string(&self.glbb).unwrap(),
@@ -58,21 +60,27 @@ impl eframe::App for App {
                     |ui| {
                         if ui.button("V").clicked() {
                             self.glbb.pos.y -= 100.0;
+                            self.glbb.mark_dirty();
                         }
 
                         if ui.button("V\nV").clicked() {
-                            self.glbb.vertical.fall();
+                            self.glbb.fall();
                         }
 
                         let max = self.glbb.pos_y_max();
 
-                        ui.add(
-                            slider::Slider::new(
-                                &mut self.glbb.pos.y,
-                                0f32..=max,
+                        if ui
+                            .add(
+                                slider::Slider::new(
+                                    &mut self.glbb.pos.y,
+                                    0f32..=max,
+                                )
+                                .vertical(),
                             )
-                            .vertical(),
-                        );
+                            .changed()
+                        {
+                            self.glbb.mark_dirty();
+                        }
                     },
                 );
             });
@@ -82,13 +90,19 @@ impl eframe::App for App {
             |ui| {
                 let max = self.glbb.pos_x_max();
                 ui.add_enabled_ui(enabled, |ui| {
-                    ui.add_sized(
-                        [ui.available_width(), 20.0],
-                        slider::Slider::new(
-                            &mut self.glbb.pos.x,
-                            0f32..=max,
-                        ),
-                    );
+                    if ui
+                        .add_sized(
+                            [ui.available_width(), 20.0],
+                            slider::Slider::new(
+                                &mut self.glbb.pos.x,
+                                0f32..=max,
+                            )
+                            .soft_notches(4),
+                        )
+                        .changed()
+                    {
+                        self.glbb.mark_dirty();
+                    }
                 });
 
                 ui.horizontal(|ui| {
@@ -109,9 +123,7 @@ impl eframe::App for App {
                             )
                             .clicked()
                         {
-                            self.glbb
-                                .horizontal
-                                .play_left();
+                            self.glbb.play_left();
                         }
 
                         if ui
@@ -122,46 +134,72 @@ impl eframe::App for App {
                             .clicked()
                         {
                             self.glbb.pos.x -= 100.0;
+                            self.glbb.mark_dirty();
                         }
 
-                        ui.add_sized(
+                        let horizontal =
+                            &mut self.glbb.horizontal;
+                        let response = ui.add_sized(
                             [width * 0.2, height],
-                            egui::DragValue::new(
-                                &mut self
-                                    .glbb
-                                    .horizontal
-                                    .velocity,
+                            egui::DragValue::from_get_set(
+                                |it| {
+                                    if let Some(it) = it {
+                                        horizontal
+                                            .set_velocity(it);
+                                    }
+                                    horizontal.launch_velocity()
+                                },
                             )
                             .prefix("velocity: ")
                             .suffix(" m/s")
                             .clamp_range(0f32..=f32::MAX),
                         );
+                        if response.changed() {
+                            self.glbb.mark_dirty();
+                        }
                     });
 
                     if ui
                         .add_sized(
                             [width * 0.2, height],
-                            egui::Button::new("| |"),
+                            egui::Button::new(
+                                if self.glbb.is_paused() {
+                                    "|>"
+                                } else {
+                                    "| |"
+                                },
+                            ),
                         )
                         .clicked()
                     {
-                        self.glbb.horizontal.stop();
-                        self.glbb.vertical.stop();
+                        if self.glbb.is_paused() {
+                            self.glbb.resume();
+                        } else {
+                            self.glbb.pause();
+                        }
                     }
 
                     ui.add_enabled_ui(enabled, |ui| {
-                        ui.add_sized(
+                        let horizontal =
+                            &mut self.glbb.horizontal;
+                        let response = ui.add_sized(
                             [width * 0.2, height],
-                            egui::DragValue::new(
-                                &mut self
-                                    .glbb
-                                    .horizontal
-                                    .acceleration,
+                            egui::DragValue::from_get_set(
+                                |it| {
+                                    if let Some(it) = it {
+                                        horizontal
+                                            .set_acceleration(it);
+                                    }
+                                    horizontal.acceleration
+                                },
                             )
                             .prefix("acceleration: ")
                             .suffix(" m/s²")
                             .clamp_range(1f32..=f32::MAX),
                         );
+                        if response.changed() {
+                            self.glbb.mark_dirty();
+                        }
 
                         if ui
                             .add_sized(
@@ -171,6 +209,7 @@ impl eframe::App for App {
                             .clicked()
                         {
                             self.glbb.pos.x += 100.0;
+                            self.glbb.mark_dirty();
                         }
 
                         if ui
@@ -180,50 +219,28 @@ impl eframe::App for App {
                             )
                             .clicked()
                         {
-                            self.glbb
-                                .horizontal
-                                .play_right();
+                            self.glbb.play_right();
                         }
                     });
                 })
             },
         );
 
-        let response = egui::CentralPanel::default().show(ctx, |ui| {
-            // make GLBBWidget expand to minimum available size.
-            ui.vertical_centered_justified(|ui| {
-                ui.with_layout(
-                    egui::Layout::left_to_right().with_cross_justify(true),
-                    |ui| {
-                        let response = GLBBWidget::new(&mut self.glbb)
-                            .show(ui)
-                            .interact(egui::Sense::click_and_drag());
-
-                        if enabled && !self.glbb.is_play() {
-                            let dragged_by_secondary =
-                                response.dragged_by(egui::PointerButton::Secondary);
-                            let drag_released = response.drag_released();
-
-                            {
-                                let mut memory = ui.memory();
-                                let is_dragged = memory.data.get_temp_mut_or_default::<bool>(
-                                    egui::Id::new("glbb-dragged-with-right-button"),
-                                );
-
-                                if *is_dragged && drag_released {
-                                    self.glbb.vertical.fall();
-                                }
-
-                                *is_dragged = dragged_by_secondary;
-                            }
-                            if let Some(pos) = response.interact_pointer_pos() {
-                                self.glbb.pos = self.glbb.pos_from_screen(response.rect, pos);
-                            }
-                        }
-                    },
-                );
-            })
-        });
+        let response =
+            egui::CentralPanel::default().show(ctx, |ui| {
+                // make GLBBWidget expand to minimum available size.
+                ui.vertical_centered_justified(|ui| {
+                    ui.with_layout(
+                        egui::Layout::left_to_right()
+                            .with_cross_justify(true),
+                        |ui| {
+                            GLBBWidget::new(&mut self.glbb)
+                                .interactive(enabled)
+                                .show(ui);
+                        },
+                    );
+                })
+            });
 
         self.size = response.response.rect.size();
     }
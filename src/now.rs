@@ -1,6 +1,14 @@
 use std::time::{Duration, Instant};
 
-pub struct Now(Instant);
+pub struct Now(Clock);
+
+/// sumber waktu dari [`Now`]
+enum Clock {
+    System(Instant),
+    /// jam buatan yang hanya maju lewat [`Now::advance`],
+    /// isinya waktu sejak reset terakhir
+    Manual(Duration),
+}
 
 impl std::fmt::Debug for Now {
     fn fmt(
@@ -13,16 +21,107 @@ impl std::fmt::Debug for Now {
 
 impl Default for Now {
     fn default() -> Self {
-        Self(Instant::now())
+        Self(Clock::System(Instant::now()))
     }
 }
 
 impl Now {
+    /// jam yang tidak mengikuti waktu sistem, hanya maju
+    /// lewat [`Self::advance`]. untuk simulasi tanpa layar
+    /// dan pengujian
+    pub fn manual() -> Self {
+        Self(Clock::Manual(Duration::ZERO))
+    }
+
+    /// jam sistem yang mulai dihitung dari `instant`,
+    /// ditujukan untuk pengujian tanpa harus menunggu
+    pub fn new_at(instant: Instant) -> Self {
+        Self(Clock::System(instant))
+    }
+
+    /// jam manual yang `elapsed` awalnya sudah `elapsed`,
+    /// ditujukan untuk pengujian
+    pub fn with_elapsed(elapsed: Duration) -> Self {
+        Self(Clock::Manual(elapsed))
+    }
+
+    /// majukan jam manual sejauh `d`, tidak berpengaruh pada
+    /// jam sistem
+    pub fn advance(&mut self, d: Duration) {
+        if let Clock::Manual(elapsed) = &mut self.0 {
+            *elapsed += d;
+        }
+    }
+
     pub fn elapsed(&self) -> Duration {
-        self.0.elapsed()
+        match &self.0 {
+            Clock::System(instant) => instant.elapsed(),
+            Clock::Manual(elapsed) => *elapsed,
+        }
+    }
+
+    /// waktu berlalu dalam detik
+    pub fn elapsed_secs(&self) -> f64 {
+        self.elapsed().as_secs_f64()
+    }
+
+    /// waktu berlalu dalam detik, versi f32
+    pub fn elapsed_secs_f32(&self) -> f32 {
+        self.elapsed().as_secs_f32()
     }
 
     pub fn reset(&mut self) {
-        self.0 = Instant::now();
+        match &mut self.0 {
+            Clock::System(instant) => {
+                *instant = Instant::now()
+            }
+            Clock::Manual(elapsed) => {
+                *elapsed = Duration::ZERO
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_secs_matches_elapsed() {
+        let mut now = Now::manual();
+        for millis in [0, 1, 16, 333, 2500] {
+            now.advance(Duration::from_millis(millis));
+            assert_eq!(
+                now.elapsed_secs(),
+                now.elapsed().as_secs_f64()
+            );
+            assert_eq!(
+                now.elapsed_secs_f32(),
+                now.elapsed().as_secs_f32()
+            );
+        }
+    }
+
+    #[test]
+    fn advance_moves_only_the_manual_clock() {
+        let mut now = Now::manual();
+        assert_eq!(now.elapsed(), Duration::ZERO);
+
+        now.advance(Duration::from_millis(250));
+        now.advance(Duration::from_nanos(7));
+        assert_eq!(
+            now.elapsed(),
+            Duration::from_millis(250)
+                + Duration::from_nanos(7)
+        );
+
+        now.reset();
+        assert_eq!(now.elapsed(), Duration::ZERO);
+
+        let mut system = Now::new_at(Instant::now());
+        system.advance(Duration::from_secs(3600));
+        assert!(
+            system.elapsed() < Duration::from_secs(3600)
+        );
     }
 }
@@ -0,0 +1,187 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    time::Duration,
+};
+
+use crate::GLBBState;
+
+/// lintasan hasil pengukuran yang diputar ulang tanpa
+/// fisika, hanya memindahkan posisi bola
+#[derive(Clone, Debug, Default)]
+pub struct Playback {
+    /// titik `(waktu dalam detik, posisi)` urut menurut waktu
+    points: Vec<(f64, egui::Pos2)>,
+    time: Duration,
+}
+
+impl Playback {
+    /// baca CSV berisi baris `time,x,y`, baris pertama boleh
+    /// berupa header
+    pub fn from_csv<R: Read>(r: R) -> io::Result<Playback> {
+        let mut points = Vec::new();
+
+        for (index, line) in
+            BufReader::new(r).lines().enumerate()
+        {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match parse_row(line) {
+                // waktu atau posisi tak hingga dan NaN
+                // tidak bisa diputar, lewati saja
+                Some((time, pos))
+                    if !time.is_finite()
+                        || !pos.is_finite() => {}
+                Some(point) => points.push(point),
+                // header
+                None if index == 0 => {}
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "baris {} bukan time,x,y: {:?}",
+                            index + 1,
+                            line
+                        ),
+                    ))
+                }
+            }
+        }
+
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Ok(Playback {
+            points,
+            time: Duration::ZERO,
+        })
+    }
+
+    pub fn points(&self) -> &[(f64, egui::Pos2)] {
+        &self.points
+    }
+
+    /// lama seluruh rekaman
+    pub fn duration(&self) -> Duration {
+        self.points.last().map_or(Duration::ZERO, |it| {
+            Duration::try_from_secs_f64(it.0.max(0.0))
+                .unwrap_or(Duration::MAX)
+        })
+    }
+
+    /// waktu putar sekarang
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time >= self.duration()
+    }
+
+    /// posisi pada waktu `time` detik, diinterpolasi linear
+    /// antar titik. di luar rekaman memakai titik terdekat
+    pub fn pos_at(&self, time: f64) -> Option<egui::Pos2> {
+        let index =
+            self.points.partition_point(|it| it.0 <= time);
+
+        match (
+            index.checked_sub(1).map(|it| self.points[it]),
+            self.points.get(index),
+        ) {
+            (Some((t0, p0)), Some(&(t1, p1))) => {
+                let t = ((time - t0) / (t1 - t0)) as f32;
+                Some(p0 + (p1 - p0) * t)
+            }
+            (Some((_, pos)), None)
+            | (None, Some(&(_, pos))) => Some(pos),
+            (None, None) => None,
+        }
+    }
+
+    /// majukan waktu putar sejauh `dt` dan pindahkan bola
+    pub fn step(
+        &mut self,
+        state: &mut GLBBState,
+        dt: Duration,
+    ) {
+        self.time = self.time.saturating_add(dt);
+        if let Some(pos) =
+            self.pos_at(self.time.as_secs_f64())
+        {
+            state.pos = pos;
+            state.mark_dirty();
+        }
+    }
+
+    /// putar dari awal
+    pub fn rewind(&mut self) {
+        self.time = Duration::ZERO;
+    }
+}
+
+fn parse_row(line: &str) -> Option<(f64, egui::Pos2)> {
+    let mut cells =
+        line.split(',').map(|it| it.trim().parse::<f64>());
+
+    let time = cells.next()?.ok()?;
+    let x = cells.next()?.ok()?;
+    let y = cells.next()?.ok()?;
+    if cells.next().is_some() {
+        return None;
+    }
+
+    Some((time, egui::pos2(x as f32, y as f32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "time,x,y
+0.0,10,100
+1.0,30,60
+inf,0,0
+2.0,30,NaN
+2.0,50,20
+";
+
+    #[test]
+    fn interpolates_between_rows_and_skips_bad_ones() {
+        let playback =
+            Playback::from_csv(CSV.as_bytes()).unwrap();
+
+        assert_eq!(playback.points().len(), 3);
+        assert_eq!(
+            playback.duration(),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            playback.pos_at(0.5),
+            Some(egui::pos2(20.0, 80.0))
+        );
+        assert_eq!(
+            playback.pos_at(1.5),
+            Some(egui::pos2(40.0, 40.0))
+        );
+        assert_eq!(
+            playback.pos_at(9.0),
+            Some(egui::pos2(50.0, 20.0))
+        );
+    }
+
+    #[test]
+    fn step_moves_the_ball_and_marks_it_dirty() {
+        let mut playback =
+            Playback::from_csv(CSV.as_bytes()).unwrap();
+        let mut state = GLBBState::default();
+        state.take_dirty();
+
+        playback
+            .step(&mut state, Duration::from_millis(500));
+
+        assert_eq!(state.pos, egui::pos2(20.0, 80.0));
+        assert!(state.take_dirty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use crate::{GLBBState, Now};
+
+/// aksi pengguna yang bisa direkam dan diputar ulang
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Action {
+    PlayLeft,
+    PlayRight,
+    Fall,
+    Stop,
+    /// bola dipindah ke posisi tertentu (drag)
+    SetPos(egui::Pos2),
+    SetVelocity(f64),
+    SetAcceleration(f64),
+}
+
+impl Action {
+    /// terapkan aksi ke state
+    pub fn apply(&self, state: &mut GLBBState) {
+        match *self {
+            Action::PlayLeft => state.play_left(),
+            Action::PlayRight => state.play_right(),
+            Action::Fall => state.fall(),
+            Action::Stop => {
+                state.horizontal.stop();
+                state.vertical.stop();
+            }
+            Action::SetPos(pos) => {
+                state.pos = pos;
+                state.mark_dirty();
+            }
+            Action::SetVelocity(velocity) => {
+                state.horizontal.set_velocity(velocity)
+            }
+            Action::SetAcceleration(acceleration) => state
+                .horizontal
+                .set_acceleration(acceleration),
+        }
+    }
+}
+
+/// perekam aksi beserta waktu terjadinya, relatif
+/// terhadap pembuatan perekam
+#[derive(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Recorder {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    start: Now,
+    events: Vec<(Duration, Action)>,
+}
+
+impl Recorder {
+    /// perekam yang mencatat waktu dari `clock`, misal
+    /// [`Now::manual`] supaya rekaman tidak bergantung pada
+    /// waktu sistem
+    pub fn with_clock(clock: Now) -> Self {
+        Self {
+            start: clock,
+            events: Vec::new(),
+        }
+    }
+
+    /// majukan jam perekam, hanya berpengaruh pada jam
+    /// manual. lihat [`Now::advance`]
+    pub fn advance(&mut self, d: Duration) {
+        self.start.advance(d);
+    }
+
+    /// rekam aksi pada waktu sekarang
+    pub fn record(&mut self, action: Action) {
+        self.events.push((self.start.elapsed(), action));
+    }
+
+    pub fn events(&self) -> &[(Duration, Action)] {
+        &self.events
+    }
+
+    /// putar ulang semua aksi ke `state` dengan langkah
+    /// waktu tetap `dt`, sehingga hasilnya selalu sama.
+    /// `clock` ikut maju sesuai waktu yang diputar.
+    ///
+    /// `dt` nol berarti bola langsung dilangkahkan ke waktu
+    /// aksi berikutnya. simulasi berhenti tepat setelah aksi
+    /// terakhir, lanjutkan dengan [`GLBBState::step`] bila
+    /// perlu.
+    pub fn replay(
+        &self,
+        state: &mut GLBBState,
+        clock: &mut Now,
+        dt: Duration,
+    ) {
+        let mut time = Duration::ZERO;
+        let mut step = |state: &mut GLBBState, d| {
+            state.step(d);
+            clock.advance(d);
+        };
+
+        for (at, action) in self.events.iter() {
+            while !dt.is_zero() && time + dt <= *at {
+                step(state, dt);
+                time += dt;
+            }
+
+            if time < *at {
+                step(state, *at - time);
+                time = *at;
+            }
+
+            action.apply(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_state() -> GLBBState {
+        let mut state = GLBBState::default();
+        state.set_bounds(egui::vec2(800.0, 600.0));
+        state.pos = egui::pos2(100.0, 300.0);
+        state.horizontal.set_velocity(400.0);
+        state.horizontal.set_acceleration(50.0);
+        state
+    }
+
+    #[test]
+    fn replay_reproduces_the_recorded_run() {
+        let dt = Duration::from_millis(16);
+        let script = [
+            (0, Action::PlayRight),
+            (20, Action::SetVelocity(250.0)),
+            (15, Action::SetAcceleration(120.0)),
+        ];
+
+        let mut original = new_state();
+        let mut recorder =
+            Recorder::with_clock(Now::manual());
+        for (frames, action) in script {
+            for _ in 0..frames {
+                original.step(dt);
+                recorder.advance(dt);
+            }
+            action.apply(&mut original);
+            recorder.record(action);
+        }
+
+        let mut replayed = new_state();
+        let mut clock = Now::manual();
+        recorder.replay(&mut replayed, &mut clock, dt);
+        assert_eq!(clock.elapsed(), dt * 35);
+        for _ in 0..30 {
+            original.step(dt);
+            replayed.step(dt);
+        }
+
+        assert!(original.is_play());
+        assert!(original.approx_eq(&replayed, 1e-6));
+    }
+
+    #[test]
+    fn zero_dt_steps_straight_to_each_action() {
+        let mut recorder =
+            Recorder::with_clock(Now::manual());
+        recorder.record(Action::PlayRight);
+        recorder.advance(Duration::from_millis(500));
+        recorder.record(Action::Stop);
+
+        let mut state = new_state();
+        let mut clock = Now::manual();
+        recorder.replay(
+            &mut state,
+            &mut clock,
+            Duration::ZERO,
+        );
+
+        assert!(!state.is_play());
+        assert!(state.pos.x > 100.0, "{:?}", state.pos);
+        assert_eq!(
+            clock.elapsed(),
+            Duration::from_millis(500)
+        );
+    }
+}
@@ -1,16 +1,31 @@
-use std::ops::RangeInclusive;
+use std::{ops::RangeInclusive, sync::Arc, time::Duration};
 
 use eframe::{emath, epaint};
 use egui::{
-    lerp, pos2, remap, remap_clamp, vec2, Key, NumExt,
-    Pos2, Rect, Response, Sense, SliderOrientation, Widget,
-    WidgetInfo,
+    epaint::Galley, lerp, pos2, remap, remap_clamp, vec2,
+    CursorIcon, Key, NumExt, PointerButton, Pos2, Rect,
+    Response, Sense, Vec2, Widget, WidgetInfo,
 };
 
+pub use egui::SliderOrientation;
+
+/// How the handle of a [`Slider`] is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HandleShape {
+    /// A circle centered on the rail.
+    #[default]
+    Circle,
+    /// A triangle beside the rail, pointing at it like a ruler marker:
+    /// above a horizontal rail and to the left of a vertical one.
+    Triangle,
+}
+
 /// Combined into one function (rather than two) to make it easier
 /// for the borrow checker.
 type GetSetValue<'a> =
     Box<dyn 'a + FnMut(Option<f64>) -> f64>;
+type NumFormatter<'a> = Box<dyn 'a + Fn(f64) -> String>;
+type NumParser<'a> = Box<dyn 'a + Fn(&str) -> Option<f64>>;
 
 fn get(get_set_value: &mut GetSetValue<'_>) -> f64 {
     (get_set_value)(None)
@@ -29,11 +44,26 @@ pub struct Slider<'a> {
     spec: SliderSpec,
     text: String,
     step: Option<f64>,
+    snap_on_release: bool,
+    relative_drag: bool,
+    show_range_labels: bool,
+    animated: bool,
+    /// Only whole numbers can be picked, see [`Self::integer`].
+    integer: bool,
+    soft_notches: Option<usize>,
+    handle_shape: HandleShape,
+    suffix: String,
+    reset_value: Option<f64>,
+    interactive: bool,
+    custom_formatter: Option<NumFormatter<'a>>,
+    custom_parser: Option<NumParser<'a>>,
     min_decimals: usize,
     max_decimals: Option<usize>,
+    /// Length of the rail in points, used to pick the display precision.
+    rail_length: f32,
 }
 
-struct SliderSpec {
+pub(crate) struct SliderSpec {
     logarithmic: bool,
     /// For logarithmic sliders, the smallest positive value we are interested in.
     /// 1 for integer sliders, maybe 1e-6 for others.
@@ -42,7 +72,21 @@ struct SliderSpec {
     /// before the slider switches to `INFINITY`, if that is the higher end.
     /// Default: INFINITY.
     largest_finite: f64,
-    //
+    /// For linear sliders, the exponent of the response curve:
+    /// the value is `lerp(range, t.powf(power))` for a handle at `t` in `0..=1`.
+    /// Default: 1 (linear).
+    power: f64,
+}
+
+impl Default for SliderSpec {
+    fn default() -> Self {
+        Self {
+            logarithmic: false,
+            smallest_positive: 1e-6,
+            largest_finite: f64::INFINITY,
+            power: 1.0,
+        }
+    }
 }
 
 impl<'a> Slider<'a> {
@@ -69,6 +113,30 @@ impl<'a> Slider<'a> {
         }
     }
 
+    /// Edit a [`Duration`] in seconds, shown with an `s` suffix.
+    /// Negative values are stored as zero and values too large for a
+    /// [`Duration`] as [`Duration::MAX`].
+    pub fn duration(
+        value: &'a mut Duration,
+        range: RangeInclusive<Duration>,
+    ) -> Self {
+        let range_f64 = range.start().as_secs_f64()
+            ..=range.end().as_secs_f64();
+        Self::from_get_set(
+            range_f64,
+            move |v: Option<f64>| {
+                if let Some(v) = v {
+                    *value = Duration::try_from_secs_f64(
+                        v.max(0.0),
+                    )
+                    .unwrap_or(Duration::MAX);
+                }
+                value.as_secs_f64()
+            },
+        )
+        .suffix("s")
+    }
+
     pub fn from_get_set(
         range: RangeInclusive<f64>,
         get_set_value: impl 'a + FnMut(Option<f64>) -> f64,
@@ -77,17 +145,26 @@ impl<'a> Slider<'a> {
             get_set_value: Box::new(get_set_value),
             range,
             clamp_to_range: false,
-            spec: SliderSpec {
-                logarithmic: false,
-                smallest_positive: 1e-6,
-                largest_finite: f64::INFINITY,
-            },
+            spec: SliderSpec::default(),
             smart_aim: true,
             orientation: SliderOrientation::Horizontal,
             text: Default::default(),
             step: None,
+            snap_on_release: false,
+            relative_drag: false,
+            show_range_labels: false,
+            animated: false,
+            integer: false,
+            soft_notches: None,
+            handle_shape: HandleShape::default(),
+            suffix: String::new(),
+            reset_value: None,
+            interactive: true,
+            custom_formatter: None,
+            custom_parser: None,
             min_decimals: 0,
             max_decimals: None,
+            rail_length: NOMINAL_RAIL_LENGTH,
         }
     }
 
@@ -132,10 +209,27 @@ impl<'a> Slider<'a> {
     /// Helper: equivalent to `self.precision(0).smallest_positive(1.0)`.
     /// If you use one of the integer constructors (e.g. `Slider::i32`) this is called for you,
     /// but if you want to have a slider for picking integer values in an `Slider::f64`, use this.
-    pub fn integer(self) -> Self {
+    ///
+    /// The handle snaps to whole numbers while it is dragged.
+    pub fn integer(mut self) -> Self {
+        self.integer = true;
         self.fixed_decimals(0).smallest_positive(1.0)
     }
 
+    /// Use a power-law response curve: a handle at fraction `t` of the rail
+    /// gives the value `lerp(range, t.powf(gamma))`.
+    /// A `gamma` above 1 gives finer control near the start of the range.
+    ///
+    /// Default: `1.0` (linear).
+    pub fn power(mut self, gamma: f64) -> Self {
+        assert!(
+            gamma.is_finite() && gamma > 0.0,
+            "gamma must be positive"
+        );
+        self.spec.power = gamma;
+        self
+    }
+
     /// For logarithmic sliders that includes zero:
     /// what is the smallest positive value you want to be able to select?
     /// The default is `1` for integer sliders and `1e-6` for real sliders.
@@ -147,11 +241,158 @@ impl<'a> Slider<'a> {
         self
     }
 
-    pub fn vertical(mut self) -> Self {
-        self.orientation = SliderOrientation::Vertical;
+    /// Sets the minimal change of the value.
+    /// Value `0.0` effectively disables the feature. If the new value is out of range
+    /// and `clamp_to_range` is enabled, you would not have the ability to change the value.
+    ///
+    /// Default: `0.0` (disabled).
+    pub fn step_by(mut self, step: f64) -> Self {
+        self.step =
+            if step != 0.0 { Some(step) } else { None };
+        self
+    }
+
+    /// If `true`, dragging moves the value freely and it only snaps to
+    /// [`Self::step_by`] once the drag is released.
+    ///
+    /// Default: `false`.
+    pub fn snap_on_release(
+        mut self,
+        snap_on_release: bool,
+    ) -> Self {
+        self.snap_on_release = snap_on_release;
+        self
+    }
+
+    /// If `true`, pressing the rail doesn't move the handle to the pointer.
+    /// Instead the handle keeps its offset from the pointer for the whole drag.
+    ///
+    /// Default: `false`.
+    pub fn relative_drag(
+        mut self,
+        relative_drag: bool,
+    ) -> Self {
+        self.relative_drag = relative_drag;
+        self
+    }
+
+    /// If `true`, the handle moves smoothly to a value that was changed
+    /// elsewhere (e.g. by a preset button) instead of jumping to it.
+    /// The bound value itself still changes instantly.
+    ///
+    /// Default: `false`.
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = animated;
+        self
+    }
+
+    /// Divide the rail into `count` equal parts and gently pull the handle
+    /// onto the nearest division when the pointer comes within the aim
+    /// radius of it, e.g. `4` gives notches at 0, 25, 50, 75 and 100 %.
+    /// Elsewhere the handle moves freely.
+    ///
+    /// Default: no notches.
+    pub fn soft_notches(mut self, count: usize) -> Self {
+        self.soft_notches = (count > 0).then_some(count);
+        self
+    }
+
+    /// Draw the handle as a circle (default) or as a triangle pointing at
+    /// the rail.
+    pub fn handle_shape(
+        mut self,
+        handle_shape: HandleShape,
+    ) -> Self {
+        self.handle_shape = handle_shape;
+        self
+    }
+
+    /// Go back to `value` on a double-click or a middle-click.
+    ///
+    /// Default: no reset.
+    pub fn reset_value(mut self, value: f64) -> Self {
+        self.reset_value = Some(value);
+        self
+    }
+
+    /// If `false`, the slider only displays the value: it ignores the
+    /// pointer and the keyboard and is never focused.
+    ///
+    /// Default: `true`.
+    pub fn interactive(
+        mut self,
+        interactive: bool,
+    ) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Format shown values with `formatter` instead of the automatic
+    /// precision and [`Self::suffix`].
+    pub fn custom_formatter(
+        mut self,
+        formatter: impl 'a + Fn(f64) -> String,
+    ) -> Self {
+        self.custom_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Parse typed text with `parser`, e.g. to accept `"1.5k"` or `"90°"`.
+    /// Should undo [`Self::custom_formatter`]; see [`Self::parse_value`].
+    pub fn custom_parser(
+        mut self,
+        parser: impl 'a + Fn(&str) -> Option<f64>,
+    ) -> Self {
+        self.custom_parser = Some(Box::new(parser));
+        self
+    }
+
+    /// Turn text entered by the user back into a value, with the
+    /// [`Self::custom_parser`] if set. Otherwise a plain number is
+    /// accepted, optionally followed by the [`Self::suffix`].
+    pub fn parse_value(&self, text: &str) -> Option<f64> {
+        if let Some(parser) = &self.custom_parser {
+            return parser(text);
+        }
+
+        let text = text.trim();
+        let text = text
+            .strip_suffix(self.suffix.trim())
+            .unwrap_or(text);
+        text.trim().parse().ok()
+    }
+
+    /// Text shown directly after every formatted value, e.g. a unit.
+    pub fn suffix(mut self, suffix: impl ToString) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Show the start and end of the range at the ends of the rail.
+    ///
+    /// Default: `false`.
+    pub fn show_range_labels(
+        mut self,
+        show_range_labels: bool,
+    ) -> Self {
+        self.show_range_labels = show_range_labels;
+        self
+    }
+
+    /// Default: [`SliderOrientation::Horizontal`].
+    pub fn orientation(
+        mut self,
+        orientation: SliderOrientation,
+    ) -> Self {
+        self.orientation = orientation;
         self
     }
 
+    /// Shorthand for `orientation(SliderOrientation::Vertical)`.
+    pub fn vertical(self) -> Self {
+        self.orientation(SliderOrientation::Vertical)
+    }
+
     fn handle_radius(&self, rect: &Rect) -> f32 {
         let limit = match self.orientation {
             SliderOrientation::Horizontal => rect.height(),
@@ -195,13 +436,9 @@ impl<'a> Slider<'a> {
         position: f32,
         position_range: RangeInclusive<f32>,
     ) -> f64 {
-        let normalized = remap_clamp(
+        value_from_position(
             position,
             position_range,
-            0.0..=1.0,
-        ) as f64;
-        value_from_normalized(
-            normalized,
             self.range(),
             &self.spec,
         )
@@ -212,18 +449,120 @@ impl<'a> Slider<'a> {
         value: f64,
         position_range: RangeInclusive<f32>,
     ) -> f32 {
-        let normalized = normalized_from_value(
+        position_from_value(
             value,
+            position_range,
             self.range(),
             &self.spec,
-        );
-        lerp(position_range, normalized as f32)
+        )
     }
 
     fn range(&self) -> RangeInclusive<f64> {
         self.range.clone()
     }
 
+    /// The value under `screen_pos` when the rail is laid out at `rect`.
+    ///
+    /// `rect` is the rail itself, which is the slider's `Response::rect`
+    /// unless [`Self::show_range_labels`] is on. Only the coordinate along
+    /// the orientation matters.
+    pub fn value_at(
+        &self,
+        screen_pos: Pos2,
+        rect: Rect,
+    ) -> f64 {
+        let position = match self.orientation {
+            SliderOrientation::Horizontal => screen_pos.x,
+            SliderOrientation::Vertical => screen_pos.y,
+        };
+        self.value_from_position(
+            position,
+            self.position_range(&rect),
+        )
+    }
+
+    /// The handle at `center` on the rail, drawn as [`Self::handle_shape`].
+    fn handle(
+        &self,
+        center: Pos2,
+        radius: f32,
+        rail_rect: &Rect,
+        fill: egui::Color32,
+        stroke: egui::Stroke,
+    ) -> epaint::Shape {
+        match self.handle_shape {
+            HandleShape::Circle => {
+                epaint::Shape::Circle(epaint::CircleShape {
+                    center,
+                    radius,
+                    fill,
+                    stroke,
+                })
+            }
+            HandleShape::Triangle => {
+                let (tip, back, across) = match self
+                    .orientation
+                {
+                    SliderOrientation::Horizontal => (
+                        pos2(center.x, rail_rect.top()),
+                        vec2(0.0, -radius),
+                        vec2(radius * 0.6, 0.0),
+                    ),
+                    SliderOrientation::Vertical => (
+                        pos2(rail_rect.left(), center.y),
+                        vec2(-radius, 0.0),
+                        vec2(0.0, radius * 0.6),
+                    ),
+                };
+                epaint::Shape::convex_polygon(
+                    vec![
+                        tip,
+                        tip + back + across,
+                        tip + back - across,
+                    ],
+                    fill,
+                    stroke,
+                )
+            }
+        }
+    }
+
+    /// Positions of the [`Self::soft_notches`] along `position_range`.
+    fn notch_positions(
+        &self,
+        position_range: RangeInclusive<f32>,
+    ) -> impl Iterator<Item = f32> {
+        self.soft_notches.into_iter().flat_map(
+            move |count| {
+                let position_range = position_range.clone();
+                (0..=count).map(move |i| {
+                    lerp(
+                        position_range.clone(),
+                        i as f32 / count as f32,
+                    )
+                })
+            },
+        )
+    }
+
+    /// The nearest notch within `aim_radius` of `position`, if any.
+    fn soft_notch(
+        &self,
+        position: f32,
+        position_range: RangeInclusive<f32>,
+        aim_radius: f32,
+    ) -> Option<f32> {
+        self.notch_positions(position_range)
+            .filter(|notch| {
+                (notch - position).abs() <= aim_radius
+            })
+            .min_by(|a, b| {
+                (a - position)
+                    .abs()
+                    .total_cmp(&(b - position).abs())
+            })
+    }
+
     fn position_range(
         &self,
         rect: &Rect,
@@ -252,7 +591,16 @@ impl<'a> Slider<'a> {
         }
     }
 
-    fn set_value(&mut self, mut value: f64) {
+    fn set_value(&mut self, value: f64) {
+        self.set_value_with(value, true);
+    }
+
+    /// Like `set_value`, but only snaps to `step` if `snap` is set.
+    fn set_value_with(
+        &mut self,
+        mut value: f64,
+        snap: bool,
+    ) {
         if self.clamp_to_range {
             let start = *self.range.start();
             let end = *self.range.end();
@@ -265,8 +613,8 @@ impl<'a> Slider<'a> {
                 max_decimals,
             );
         }
-        if let Some(step) = self.step {
-            value = (value / step).round() * step;
+        if let Some(step) = self.step.filter(|_| snap) {
+            value = snap_to_step(value, step);
         }
         set(&mut self.get_set_value, value);
     }
@@ -311,58 +659,335 @@ impl<'a> Slider<'a> {
         }
     }
 
-    fn allocate_space(
+    /// The position along the rail a drag at `pointer_position_2d` aims at.
+    ///
+    /// With `relative_drag` the offset between the pointer and the handle is
+    /// remembered on the first frame of the drag, which returns `None` so the
+    /// value doesn't jump.
+    fn drag_position(
+        &mut self,
+        ui: &egui::Ui,
+        response: &Response,
+        pointer_position_2d: Pos2,
+        position_range: RangeInclusive<f32>,
+    ) -> Option<f32> {
+        let position =
+            self.pointer_position(pointer_position_2d);
+        if !self.relative_drag {
+            return Some(position);
+        }
+
+        let id = relative_drag_id(response);
+        let offset = ui.memory().data.get_temp::<f32>(id);
+        match offset {
+            Some(offset) => Some(position + offset),
+            None => {
+                let value = self.get_value();
+                let handle = self.position_from_value(
+                    value,
+                    position_range,
+                );
+                ui.memory()
+                    .data
+                    .insert_temp(id, handle - position);
+                None
+            }
+        }
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        if let Some(formatter) = &self.custom_formatter {
+            return formatter(value);
+        }
+
+        let decimals = self.decimals_for_value(value);
+        let mut text = emath::format_with_decimals_in_range(
+            value,
+            decimals..=decimals,
+        );
+        text.push_str(&self.suffix);
+        text
+    }
+
+    /// The number of decimals used to display `value`.
+    ///
+    /// Without [`Self::max_decimals`] the precision is picked so that one
+    /// point of handle movement changes the last shown digit: a `0..=1000`
+    /// slider shows whole numbers while a `0..=1` slider shows a couple of
+    /// decimals. Trailing zeros are dropped down to [`Self::min_decimals`].
+    pub fn decimals_for_value(&self, value: f64) -> usize {
+        let max_decimals = self
+            .max_decimals
+            .unwrap_or_else(|| self.auto_decimals())
+            .max(self.min_decimals);
+        let rounded =
+            emath::round_to_decimals(value, max_decimals);
+        (self.min_decimals..max_decimals)
+            .find(|&decimals| {
+                emath::round_to_decimals(value, decimals)
+                    == rounded
+            })
+            .unwrap_or(max_decimals)
+    }
+
+    /// Decimals needed to tell apart values one point apart on the rail.
+    fn auto_decimals(&self) -> usize {
+        if let Some(step) = self.step {
+            return step_decimals(step);
+        }
+
+        let span =
+            (self.range.end() - self.range.start()).abs();
+        if !span.is_finite() || span == 0.0 {
+            return DEFAULT_MAX_DECIMALS;
+        }
+
+        let value_per_point =
+            span / self.rail_length.max(1.0) as f64;
+        (-value_per_point.log10())
+            .ceil()
+            .clamp(0.0, DEFAULT_MAX_DECIMALS as f64)
+            as usize
+    }
+
+    /// Lay out the `[start, end]` labels of the range, if enabled.
+    fn range_labels(
         &self,
-        ui: &mut egui::Ui,
-    ) -> Response {
-        const SLIDER_WIDTH: f32 = 15f32;
-        let desired_size = match self.orientation {
+        ui: &egui::Ui,
+    ) -> Option<[Arc<Galley>; 2]> {
+        if !self.show_range_labels {
+            return None;
+        }
+
+        let font_id =
+            egui::TextStyle::Small.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        Some([*self.range.start(), *self.range.end()].map(
+            |value| {
+                ui.fonts().layout_no_wrap(
+                    self.format_value(value),
+                    font_id.clone(),
+                    color,
+                )
+            },
+        ))
+    }
+
+    /// The part of the allocated `rect` used by the rail and handle,
+    /// leaving room for the range labels at its ends.
+    fn slider_rect(
+        &self,
+        rect: Rect,
+        labels: Option<&[Arc<Galley>; 2]>,
+        gap: f32,
+    ) -> Rect {
+        let [start, end] = match labels {
+            Some(labels) => labels,
+            None => return rect,
+        };
+
+        match self.orientation {
             SliderOrientation::Horizontal => {
-                vec2(ui.available_width(), SLIDER_WIDTH)
+                Rect::from_min_max(
+                    pos2(
+                        rect.left() + start.size().x + gap,
+                        rect.center().y
+                            - SLIDER_WIDTH / 2.0,
+                    ),
+                    pos2(
+                        rect.right() - end.size().x - gap,
+                        rect.center().y
+                            + SLIDER_WIDTH / 2.0,
+                    ),
+                )
             }
             SliderOrientation::Vertical => {
-                vec2(SLIDER_WIDTH, ui.available_height())
+                Rect::from_min_max(
+                    pos2(
+                        rect.center().x
+                            - SLIDER_WIDTH / 2.0,
+                        rect.top() + end.size().y + gap,
+                    ),
+                    pos2(
+                        rect.center().x
+                            + SLIDER_WIDTH / 2.0,
+                        rect.bottom()
+                            - start.size().y
+                            - gap,
+                    ),
+                )
             }
+        }
+    }
+
+    fn paint_range_labels(
+        &self,
+        ui: &egui::Ui,
+        rect: Rect,
+        [start, end]: [Arc<Galley>; 2],
+    ) {
+        let (start_pos, end_pos) = match self.orientation {
+            SliderOrientation::Horizontal => (
+                pos2(
+                    rect.left(),
+                    rect.center().y - start.size().y / 2.0,
+                ),
+                pos2(
+                    rect.right() - end.size().x,
+                    rect.center().y - end.size().y / 2.0,
+                ),
+            ),
+            SliderOrientation::Vertical => (
+                pos2(
+                    rect.center().x - start.size().x / 2.0,
+                    rect.bottom() - start.size().y,
+                ),
+                pos2(
+                    rect.center().x - end.size().x / 2.0,
+                    rect.top(),
+                ),
+            ),
         };
 
-        ui.allocate_exact_size(
-            desired_size,
-            Sense::click_and_drag(),
-        )
-        .1
+        ui.painter().galley(start_pos, start);
+        ui.painter().galley(end_pos, end);
+    }
+
+    fn allocate_space(
+        &self,
+        ui: &mut egui::Ui,
+        labels: Option<&[Arc<Galley>; 2]>,
+    ) -> Response {
+        let label_size = labels
+            .into_iter()
+            .flatten()
+            .fold(Vec2::ZERO, |size, it| {
+                size.max(it.size())
+            });
+        let desired_size = match self.orientation {
+            SliderOrientation::Horizontal => vec2(
+                ui.available_width(),
+                SLIDER_WIDTH.max(label_size.y),
+            ),
+            SliderOrientation::Vertical => vec2(
+                SLIDER_WIDTH.max(label_size.x),
+                ui.available_height(),
+            ),
+        };
+
+        let sense = if self.interactive {
+            Sense::click_and_drag()
+        } else {
+            Sense::hover()
+        };
+        ui.allocate_exact_size(desired_size, sense).1
     }
 }
 
 impl<'a> Widget for Slider<'a> {
     fn ui(mut self, ui: &mut egui::Ui) -> Response {
-        let response = self.allocate_space(ui);
-        let &rect = &response.rect;
+        self.rail_length = match self.orientation {
+            SliderOrientation::Horizontal => {
+                ui.available_width()
+            }
+            SliderOrientation::Vertical => {
+                ui.available_height()
+            }
+        };
+        let labels = self.range_labels(ui);
+        let cursor = match self.orientation {
+            SliderOrientation::Horizontal => {
+                CursorIcon::ResizeHorizontal
+            }
+            SliderOrientation::Vertical => {
+                CursorIcon::ResizeVertical
+            }
+        };
+        let old_value = self.get_value();
+        let mut response =
+            self.allocate_space(ui, labels.as_ref());
+        if self.interactive {
+            response = response.on_hover_cursor(cursor);
+        }
+        let rect = self.slider_rect(
+            response.rect,
+            labels.as_ref(),
+            ui.spacing().item_spacing.x,
+        );
         let position_range = self.position_range(&rect);
 
         if let Some(pointer_position_2d) =
             response.interact_pointer_pos()
         {
-            let position =
-                self.pointer_position(pointer_position_2d);
-            let new_value = if self.smart_aim {
+            if let Some(position) = self.drag_position(
+                ui,
+                &response,
+                pointer_position_2d,
+                position_range.clone(),
+            ) {
                 let aim_radius = ui.input().aim_radius();
-                eframe::emath::smart_aim::best_in_range_f64(
+                let new_value = if let Some(notch) = self
+                    .soft_notch(
+                        position,
+                        position_range.clone(),
+                        aim_radius,
+                    ) {
                     self.value_from_position(
-                        position - aim_radius,
+                        notch,
                         position_range.clone(),
-                    ),
+                    )
+                } else if self.smart_aim {
+                    eframe::emath::smart_aim::best_in_range_f64(
+                        self.value_from_position(
+                            position - aim_radius,
+                            position_range.clone(),
+                        ),
+                        self.value_from_position(
+                            position + aim_radius,
+                            position_range.clone(),
+                        ),
+                    )
+                } else {
                     self.value_from_position(
-                        position + aim_radius,
+                        position,
                         position_range.clone(),
-                    ),
-                )
-            } else {
-                self.value_from_position(
-                    position,
-                    position_range.clone(),
-                )
-            };
-            self.set_value(new_value);
+                    )
+                };
+                // Snap while dragging so the handle clicks between
+                // whole numbers, even with `snap_on_release`.
+                let new_value = if self.integer {
+                    new_value.round()
+                } else {
+                    new_value
+                };
+                self.set_value_with(
+                    new_value,
+                    !self.snap_on_release,
+                );
+            }
+        } else if self.relative_drag {
+            ui.memory()
+                .data
+                .remove::<f32>(relative_drag_id(&response));
+        }
+
+        if self.snap_on_release && response.drag_released()
+        {
+            let value = self.get_value();
+            self.set_value(value);
+        }
+
+        if let Some(reset_value) = self.reset_value {
+            if response.double_clicked()
+                || response
+                    .clicked_by(PointerButton::Middle)
+            {
+                self.set_value(reset_value);
+            }
+        }
+
+        if self.get_value() != old_value {
+            response.mark_changed();
         }
 
         let value = self.get_value();
@@ -421,11 +1046,42 @@ impl<'a> Widget for Slider<'a> {
                 };
                 self.set_value(new_value);
             }
+
+            // Home/End jump to the ends of the range, whatever the
+            // orientation:
+            if ui.input().key_pressed(Key::Home) {
+                self.set_value(*self.range().start());
+            }
+            if ui.input().key_pressed(Key::End) {
+                self.set_value(*self.range().end());
+            }
         }
 
         // Paint it:
         if ui.is_rect_visible(response.rect) {
-            let value = self.get_value();
+            let mut value = self.get_value();
+
+            if self.animated {
+                // Follow the pointer directly while dragging.
+                let animation_time = if response.dragged() {
+                    0.0
+                } else {
+                    ui.style().animation_time
+                };
+                value = ui.ctx().animate_value_with_time(
+                    animated_value_id(&response),
+                    value as f32,
+                    animation_time,
+                ) as f64;
+            }
+
+            if let Some(labels) = labels {
+                self.paint_range_labels(
+                    ui,
+                    response.rect,
+                    labels,
+                );
+            }
 
             let rail_radius = ui.painter().round_to_pixel(
                 self.rail_radius_limit(&rect),
@@ -433,8 +1089,10 @@ impl<'a> Widget for Slider<'a> {
             let rail_rect =
                 self.rail_rect(&rect, rail_radius);
 
-            let position_1d = self
-                .position_from_value(value, position_range);
+            let position_1d = self.position_from_value(
+                value,
+                position_range.clone(),
+            );
 
             let visuals = ui.style().interact(&response);
             ui.painter().add(epaint::RectShape {
@@ -452,16 +1110,31 @@ impl<'a> Widget for Slider<'a> {
                 // stroke: ui.visuals().widgets.inactive.bg_stroke,
             });
 
+            for notch in
+                self.notch_positions(position_range)
+            {
+                ui.painter().circle_filled(
+                    self.marker_center(notch, &rail_rect),
+                    rail_radius * 0.5,
+                    ui.visuals()
+                        .widgets
+                        .inactive
+                        .fg_stroke
+                        .color,
+                );
+            }
+
             let center =
                 self.marker_center(position_1d, &rail_rect);
 
-            ui.painter().add(epaint::CircleShape {
+            ui.painter().add(self.handle(
                 center,
-                radius: self.handle_radius(&rect)
+                self.handle_radius(&rect)
                     + visuals.expansion,
-                fill: visuals.bg_fill,
-                stroke: visuals.fg_stroke,
-            });
+                &rail_rect,
+                visuals.bg_fill,
+                visuals.fg_stroke,
+            ));
         }
 
         response
@@ -469,21 +1142,308 @@ impl<'a> Widget for Slider<'a> {
 }
 
 // ----------------------------------------------------------------------------
-// Helpers for converting slider range to/from normalized [0-1] range.
-// Always clamps.
-// Logarithmic sliders are allowed to include zero and infinity,
-// even though mathematically it doesn't make sense.
 
-use std::f64::INFINITY;
+/// Pad for picking a 2D value by dragging a single handle, e.g. the position
+/// of the ball. The y axis goes up, like [`Slider::vertical`].
+pub struct XyPad<'a> {
+    value: &'a mut Pos2,
+    x_range: RangeInclusive<f64>,
+    y_range: RangeInclusive<f64>,
+    spec: SliderSpec,
+    smart_aim: bool,
+}
 
-/// When the user asks for an infinitely large range (e.g. logarithmic from zero),
-/// give a scale that this many orders of magnitude in size.
-const INF_RANGE_MAGNITUDE: f64 = 10.0;
+impl<'a> XyPad<'a> {
+    pub fn new(
+        value: &'a mut Pos2,
+        x_range: RangeInclusive<f32>,
+        y_range: RangeInclusive<f32>,
+    ) -> Self {
+        Self {
+            value,
+            x_range: *x_range.start() as f64
+                ..=*x_range.end() as f64,
+            y_range: *y_range.start() as f64
+                ..=*y_range.end() as f64,
+            spec: SliderSpec::default(),
+            smart_aim: true,
+        }
+    }
 
-fn value_from_normalized(
-    normalized: f64,
-    range: RangeInclusive<f64>,
-    spec: &SliderSpec,
+    /// If enabled, each axis picks nice, round values near the pointer,
+    /// like [`Slider`] does.
+    ///
+    /// Default: `true`.
+    pub fn smart_aim(mut self, smart_aim: bool) -> Self {
+        self.smart_aim = smart_aim;
+        self
+    }
+
+    /// Physical location of the x and y axis on the screen.
+    fn position_ranges(
+        rect: &Rect,
+    ) -> (RangeInclusive<f32>, RangeInclusive<f32>) {
+        let rect = rect.shrink(XY_PAD_HANDLE_RADIUS);
+        (
+            rect.left()..=rect.right(),
+            rect.bottom()..=rect.top(),
+        )
+    }
+
+    /// The value under `pointer_position` when the pad is at `rect`.
+    /// With smart aim, each axis picks the roundest value within `aim_radius`.
+    fn value_from_position(
+        &self,
+        pointer_position: Pos2,
+        rect: &Rect,
+        aim_radius: f32,
+    ) -> Pos2 {
+        let (x_position_range, y_position_range) =
+            Self::position_ranges(rect);
+        pos2(
+            self.axis_value(
+                pointer_position.x,
+                x_position_range,
+                self.x_range.clone(),
+                aim_radius,
+            ) as f32,
+            self.axis_value(
+                pointer_position.y,
+                y_position_range,
+                self.y_range.clone(),
+                aim_radius,
+            ) as f32,
+        )
+    }
+
+    fn axis_value(
+        &self,
+        position: f32,
+        position_range: RangeInclusive<f32>,
+        range: RangeInclusive<f64>,
+        aim_radius: f32,
+    ) -> f64 {
+        let value_at = |position| {
+            value_from_position(
+                position,
+                position_range.clone(),
+                range.clone(),
+                &self.spec,
+            )
+        };
+
+        if self.smart_aim {
+            emath::smart_aim::best_in_range_f64(
+                value_at(position - aim_radius),
+                value_at(position + aim_radius),
+            )
+        } else {
+            value_at(position)
+        }
+    }
+
+    /// Where the handle for `value` is when the pad is at `rect`.
+    fn position_from_value(
+        &self,
+        value: Pos2,
+        rect: &Rect,
+    ) -> Pos2 {
+        let (x_position_range, y_position_range) =
+            Self::position_ranges(rect);
+        pos2(
+            position_from_value(
+                value.x as f64,
+                x_position_range,
+                self.x_range.clone(),
+                &self.spec,
+            ),
+            position_from_value(
+                value.y as f64,
+                y_position_range,
+                self.y_range.clone(),
+                &self.spec,
+            ),
+        )
+    }
+}
+
+impl<'a> Widget for XyPad<'a> {
+    fn ui(self, ui: &mut egui::Ui) -> Response {
+        let (rect, mut response) = ui.allocate_exact_size(
+            ui.available_size(),
+            Sense::click_and_drag(),
+        );
+        let old_value = *self.value;
+
+        let aim_radius = ui.input().aim_radius();
+
+        if let Some(pointer_position) =
+            response.interact_pointer_pos()
+        {
+            *self.value = self.value_from_position(
+                pointer_position,
+                &rect,
+                aim_radius,
+            );
+        }
+
+        if response.has_focus() {
+            let kb_step = {
+                let input = ui.input();
+                let presses = |dec_key, inc_key| {
+                    input.num_presses(inc_key) as f32
+                        - input.num_presses(dec_key) as f32
+                };
+                // up = decrement y coordinate, which increments the value.
+                vec2(
+                    presses(
+                        Key::ArrowLeft,
+                        Key::ArrowRight,
+                    ),
+                    presses(Key::ArrowUp, Key::ArrowDown),
+                )
+            };
+
+            if kb_step != Vec2::ZERO {
+                let position = self.position_from_value(
+                    *self.value,
+                    &rect,
+                ) + kb_step;
+                *self.value = self.value_from_position(
+                    position, &rect, aim_radius,
+                );
+            }
+        }
+
+        if *self.value != old_value {
+            response.mark_changed();
+        }
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact(&response);
+            ui.painter().rect_filled(
+                rect,
+                ui.visuals().widgets.inactive.rounding,
+                ui.visuals().widgets.inactive.bg_fill,
+            );
+
+            let center = self
+                .position_from_value(*self.value, &rect);
+            let crosshair = ui
+                .visuals()
+                .widgets
+                .noninteractive
+                .bg_stroke;
+            ui.painter().hline(
+                rect.x_range(),
+                center.y,
+                crosshair,
+            );
+            ui.painter().vline(
+                center.x,
+                rect.y_range(),
+                crosshair,
+            );
+
+            ui.painter().add(epaint::CircleShape {
+                center,
+                radius: XY_PAD_HANDLE_RADIUS
+                    + visuals.expansion,
+                fill: visuals.bg_fill,
+                stroke: visuals.fg_stroke,
+            });
+        }
+
+        response
+    }
+}
+
+const XY_PAD_HANDLE_RADIUS: f32 = 6.0;
+
+/// The animation of the displayed value of a slider, stable across frames.
+fn animated_value_id(response: &Response) -> egui::Id {
+    response.id.with("animated_value")
+}
+
+/// Where the relative drag offset of a slider is kept between frames.
+fn relative_drag_id(response: &Response) -> egui::Id {
+    response.id.with("relative_drag_offset")
+}
+
+/// Thickness of the slider across its orientation.
+const SLIDER_WIDTH: f32 = 15f32;
+
+/// Most decimals picked automatically when no `max_decimals` is set.
+const DEFAULT_MAX_DECIMALS: usize = 6;
+
+/// Rail length assumed for the display precision before the slider is laid out.
+const NOMINAL_RAIL_LENGTH: f32 = 100.0;
+
+/// Snap `value` to the nearest multiple of `step`.
+///
+/// The result is rounded to the number of decimals in `step`, so a value
+/// that went through a lossy `f32` round-trip (e.g. `0.30000001`) snaps
+/// back to the same multiple instead of drifting away from it.
+fn snap_to_step(value: f64, step: f64) -> f64 {
+    let steps = (value / step).round();
+    emath::round_to_decimals(
+        steps * step,
+        step_decimals(step),
+    )
+}
+
+/// The smallest number of decimals that represents `step`, within `f32` precision.
+fn step_decimals(step: f64) -> usize {
+    const MAX_DECIMALS: usize = 15;
+    (0..MAX_DECIMALS)
+        .find(|&decimals| {
+            let scaled =
+                step.abs() * 10f64.powi(decimals as i32);
+            (scaled - scaled.round()).abs()
+                <= scaled * f32::EPSILON as f64
+        })
+        .unwrap_or(MAX_DECIMALS)
+}
+
+/// For instance, `position` is the mouse position and `position_range` is the physical location of the slider on the screen.
+pub(crate) fn value_from_position(
+    position: f32,
+    position_range: RangeInclusive<f32>,
+    range: RangeInclusive<f64>,
+    spec: &SliderSpec,
+) -> f64 {
+    let normalized =
+        remap_clamp(position, position_range, 0.0..=1.0)
+            as f64;
+    value_from_normalized(normalized, range, spec)
+}
+
+/// The inverse of [`value_from_position`].
+pub(crate) fn position_from_value(
+    value: f64,
+    position_range: RangeInclusive<f32>,
+    range: RangeInclusive<f64>,
+    spec: &SliderSpec,
+) -> f32 {
+    let normalized =
+        normalized_from_value(value, range, spec);
+    lerp(position_range, normalized as f32)
+}
+
+// ----------------------------------------------------------------------------
+// Helpers for converting slider range to/from normalized [0-1] range.
+// Always clamps.
+// Logarithmic sliders are allowed to include zero and infinity,
+// even though mathematically it doesn't make sense.
+
+/// When the user asks for an infinitely large range (e.g. logarithmic from zero),
+/// give a scale that this many orders of magnitude in size.
+const INF_RANGE_MAGNITUDE: f64 = 10.0;
+
+fn value_from_normalized(
+    normalized: f64,
+    range: RangeInclusive<f64>,
+    spec: &SliderSpec,
 ) -> f64 {
     let (min, max) = (*range.start(), *range.end());
 
@@ -547,7 +1507,10 @@ fn value_from_normalized(
             min.is_finite() && max.is_finite(),
             "You should use a logarithmic range"
         );
-        lerp(range, normalized.clamp(0.0, 1.0))
+        lerp(
+            range,
+            normalized.clamp(0.0, 1.0).powf(spec.power),
+        )
     }
 }
 
@@ -615,6 +1578,7 @@ fn normalized_from_value(
             "You should use a logarithmic range"
         );
         remap_clamp(value, range, 0.0..=1.0)
+            .powf(spec.power.recip())
     }
 }
 
@@ -626,7 +1590,7 @@ fn range_log10(
     assert!(spec.logarithmic);
     assert!(min <= max);
 
-    if min == 0.0 && max == INFINITY {
+    if min == 0.0 && max == f64::INFINITY {
         (
             spec.smallest_positive.log10(),
             INF_RANGE_MAGNITUDE,
@@ -637,7 +1601,7 @@ fn range_log10(
         } else {
             (max.log10() - INF_RANGE_MAGNITUDE, max.log10())
         }
-    } else if max == INFINITY {
+    } else if max == f64::INFINITY {
         if min < spec.largest_finite {
             (min.log10(), spec.largest_finite.log10())
         } else {
@@ -653,12 +1617,12 @@ fn range_log10(
 fn logaritmic_zero_cutoff(min: f64, max: f64) -> f64 {
     assert!(min < 0.0 && 0.0 < max);
 
-    let min_magnitude = if min == -INFINITY {
+    let min_magnitude = if min == -f64::INFINITY {
         INF_RANGE_MAGNITUDE
     } else {
         min.abs().log10().abs()
     };
-    let max_magnitude = if max == INFINITY {
+    let max_magnitude = if max == f64::INFINITY {
         INF_RANGE_MAGNITUDE
     } else {
         max.log10().abs()
@@ -666,6 +1630,751 @@ fn logaritmic_zero_cutoff(min: f64, max: f64) -> f64 {
 
     let cutoff =
         min_magnitude / (min_magnitude + max_magnitude);
-    assert!(0.0 <= cutoff && cutoff <= 1.0);
+    assert!((0.0..=1.0).contains(&cutoff));
     cutoff
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Event, Modifiers};
+
+    const SCREEN: Rect = Rect {
+        min: Pos2::ZERO,
+        max: pos2(400.0, 300.0),
+    };
+
+    /// Run one frame of `ctx` with `events`, showing `add_contents` in a
+    /// central panel.
+    fn run<R>(
+        ctx: &egui::Context,
+        events: Vec<Event>,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> R {
+        let input = egui::RawInput {
+            screen_rect: Some(SCREEN),
+            events,
+            ..Default::default()
+        };
+        let mut add_contents = Some(add_contents);
+        let mut result = None;
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                if let Some(add_contents) =
+                    add_contents.take()
+                {
+                    result = Some(add_contents(ui));
+                }
+            });
+        });
+        result.expect("the panel was not shown")
+    }
+
+    fn button(
+        pos: Pos2,
+        button: PointerButton,
+        pressed: bool,
+    ) -> Event {
+        Event::PointerButton {
+            pos,
+            button,
+            pressed,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// The events of each frame of a primary-button drag from `from` through
+    /// `to`, released at the last point.
+    fn drag(from: Pos2, to: &[Pos2]) -> Vec<Vec<Event>> {
+        let mut frames = vec![
+            vec![Event::PointerMoved(from)],
+            vec![button(
+                from,
+                PointerButton::Primary,
+                true,
+            )],
+        ];
+        frames.extend(
+            to.iter()
+                .map(|&it| vec![Event::PointerMoved(it)]),
+        );
+        let last = to.last().copied().unwrap_or(from);
+        frames.push(vec![button(
+            last,
+            PointerButton::Primary,
+            false,
+        )]);
+        frames
+    }
+
+    #[test]
+    fn f32_steps_stay_exact_multiples() {
+        let ctx = egui::Context::default();
+        let mut value = 0.0_f32;
+        let add = |ui: &mut egui::Ui, value: &mut f32| {
+            ui.add(
+                Slider::new(value, 0.0..=100.0)
+                    .step_by(10.0),
+            )
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+
+        let at = |t: f32| {
+            pos2(lerp(rect.x_range(), t), rect.center().y)
+        };
+        let path = [
+            at(0.13),
+            at(0.37),
+            at(0.71),
+            at(0.29),
+            at(0.93),
+        ];
+        for events in drag(at(0.05), &path) {
+            run(&ctx, events, |ui| add(ui, &mut value));
+            assert_eq!(
+                value,
+                (value / 10.0).round() * 10.0
+            );
+        }
+        assert_ne!(value, 0.0);
+    }
+
+    #[test]
+    fn snap_on_release_snaps_only_after_the_drag() {
+        let ctx = egui::Context::default();
+        let mut value = 0.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(
+                Slider::new(value, 0.0..=100.0)
+                    .step_by(10.0)
+                    .snap_on_release(true),
+            )
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+
+        let at = |t: f32| {
+            pos2(lerp(rect.x_range(), t), rect.center().y)
+        };
+        let frames = drag(at(0.1), &[at(0.37)]);
+        let (release, frames) =
+            frames.split_last().unwrap();
+        for events in frames.iter().cloned() {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+        assert_ne!(value % 10.0, 0.0, "{}", value);
+
+        run(&ctx, release.clone(), |ui| {
+            add(ui, &mut value)
+        });
+        assert_eq!(value, 40.0);
+    }
+
+    #[test]
+    fn non_interactive_slider_ignores_a_drag() {
+        let ctx = egui::Context::default();
+        let mut value = 20.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(
+                Slider::new(value, 0.0..=100.0)
+                    .interactive(false),
+            )
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+
+        let at = |t: f32| {
+            pos2(lerp(rect.x_range(), t), rect.center().y)
+        };
+        for events in drag(at(0.2), &[at(0.5), at(0.9)]) {
+            let response =
+                run(&ctx, events, |ui| add(ui, &mut value));
+            assert!(!response.changed());
+        }
+        assert_eq!(value, 20.0);
+    }
+
+    #[test]
+    fn custom_parser_round_trips_the_formatter() {
+        let mut value = 0.0;
+        let kilo = Slider::new(&mut value, 0.0..=10_000.0)
+            .custom_formatter(|v| {
+                format!("{}k", v / 1000.0)
+            })
+            .custom_parser(|text| {
+                let kilo: f64 = text
+                    .trim()
+                    .strip_suffix('k')?
+                    .parse()
+                    .ok()?;
+                Some(kilo * 1000.0)
+            });
+        for v in [0.0, 1500.0, 250.0, 10_000.0] {
+            let text = kilo.format_value(v);
+            assert_eq!(
+                kilo.parse_value(&text),
+                Some(v),
+                "{}",
+                text
+            );
+        }
+        assert_eq!(kilo.parse_value("1.5k"), Some(1500.0));
+        assert_eq!(kilo.parse_value("1.5"), None);
+
+        let mut value = 0.0;
+        let degrees = Slider::new(&mut value, 0.0..=360.0)
+            .custom_formatter(|v| format!("{}°", v))
+            .custom_parser(|text| {
+                text.trim()
+                    .trim_end_matches('°')
+                    .parse()
+                    .ok()
+            });
+        for v in [0.0, 45.0, 90.5, 360.0] {
+            let text = degrees.format_value(v);
+            assert_eq!(
+                degrees.parse_value(&text),
+                Some(v),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn orientation_vertical_matches_vertical() {
+        let mut a = 0.0;
+        let mut b = 0.0;
+        let by_orientation =
+            Slider::new(&mut a, 0.0..=100.0)
+                .orientation(SliderOrientation::Vertical);
+        let by_shorthand =
+            Slider::new(&mut b, 0.0..=100.0).vertical();
+        let rect = Rect::from_min_size(
+            Pos2::ZERO,
+            vec2(15.0, 200.0),
+        );
+
+        for y in [0.0, 50.0, 120.0, 200.0] {
+            let pos = pos2(3.0, y);
+            assert_eq!(
+                by_orientation.value_at(pos, rect),
+                by_shorthand.value_at(pos, rect)
+            );
+        }
+        // The top of the rail is the end of the range:
+        assert_eq!(
+            by_shorthand.value_at(Pos2::ZERO, rect),
+            100.0
+        );
+    }
+
+    #[test]
+    fn relative_drag_does_not_jump_to_the_pointer() {
+        let ctx = egui::Context::default();
+        let mut value = 20.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(
+                Slider::new(value, 0.0..=100.0)
+                    .relative_drag(true),
+            )
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+        let range = Slider::new(&mut 0.0, 0.0..=100.0)
+            .position_range(&rect);
+        let at = |t: f32| {
+            pos2(lerp(range.clone(), t), rect.center().y)
+        };
+
+        let frames = drag(at(0.8), &[at(0.9)]);
+        for events in frames[..2].iter().cloned() {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+        assert_eq!(value, 20.0);
+
+        for events in frames[2..].iter().cloned() {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+        assert!((value - 30.0).abs() < 0.5, "{}", value);
+    }
+
+    #[test]
+    fn xy_pad_maps_both_axes() {
+        let mut value = Pos2::ZERO;
+        let pad =
+            XyPad::new(&mut value, 0.0..=100.0, 0.0..=50.0)
+                .smart_aim(false);
+        // the handle travels over the rect shrunk by its radius
+        let rect = Rect::from_min_size(
+            pos2(10.0, 20.0),
+            vec2(200.0, 100.0),
+        )
+        .expand(XY_PAD_HANDLE_RADIUS);
+
+        assert_eq!(
+            pad.value_from_position(
+                rect.center(),
+                &rect,
+                0.0
+            ),
+            pos2(50.0, 25.0)
+        );
+        assert_eq!(
+            pad.value_from_position(
+                pos2(60.0, 40.0),
+                &rect,
+                0.0
+            ),
+            pos2(25.0, 40.0)
+        );
+
+        for value in [
+            pos2(0.0, 0.0),
+            pos2(12.5, 40.0),
+            pos2(100.0, 50.0),
+        ] {
+            let position =
+                pad.position_from_value(value, &rect);
+            assert_eq!(
+                pad.value_from_position(
+                    position, &rect, 0.0
+                ),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn xy_pad_drag_reports_a_change() {
+        let ctx = egui::Context::default();
+        let mut value = Pos2::ZERO;
+        let add = |ui: &mut egui::Ui, value: &mut Pos2| {
+            ui.add(XyPad::new(
+                value,
+                0.0..=100.0,
+                0.0..=100.0,
+            ))
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+
+        let mut changed = Vec::new();
+        for events in drag(
+            rect.center(),
+            &[rect.center() + vec2(20.0, -20.0)],
+        ) {
+            changed.push(
+                run(&ctx, events, |ui| add(ui, &mut value))
+                    .changed(),
+            );
+        }
+
+        assert_eq!(changed, [false, true, true, false]);
+        assert!(
+            value.x > 50.0 && value.y > 50.0,
+            "{:?}",
+            value
+        );
+    }
+
+    #[test]
+    fn xy_pad_clamps_at_the_corners() {
+        let mut value = Pos2::ZERO;
+        let pad =
+            XyPad::new(&mut value, 0.0..=100.0, 0.0..=50.0);
+        let rect = Rect::from_min_size(
+            pos2(10.0, 20.0),
+            vec2(200.0, 100.0),
+        );
+        let outside = rect.expand(30.0);
+
+        let corners = [
+            (outside.left_bottom(), pos2(0.0, 0.0)),
+            (outside.right_bottom(), pos2(100.0, 0.0)),
+            (outside.left_top(), pos2(0.0, 50.0)),
+            (outside.right_top(), pos2(100.0, 50.0)),
+        ];
+        for (position, expected) in corners {
+            assert_eq!(
+                pad.value_from_position(
+                    position, &rect, 1.0
+                ),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn animated_handle_follows_a_stable_id() {
+        let ctx = egui::Context::default();
+        let mut value = 0.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(
+                Slider::new(value, 0.0..=100.0)
+                    .animated(true),
+            )
+        };
+        let first =
+            run(&ctx, vec![], |ui| add(ui, &mut value));
+
+        value = 100.0;
+        let second =
+            run(&ctx, vec![], |ui| add(ui, &mut value));
+
+        let id = animated_value_id(&second);
+        assert_eq!(animated_value_id(&first), id);
+        // the handle is still on its way to the new value
+        let shown = ctx.animate_value_with_time(
+            id,
+            100.0,
+            ctx.style().animation_time,
+        );
+        assert!(shown < 100.0, "{}", shown);
+    }
+
+    #[test]
+    fn home_and_end_jump_to_the_range_ends() {
+        let ctx = egui::Context::default();
+        let mut value = 40.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(Slider::new(value, -10.0..=90.0))
+        };
+        let response =
+            run(&ctx, vec![], |ui| add(ui, &mut value));
+        ctx.memory().request_focus(response.id);
+
+        let press = |key| Event::Key {
+            key,
+            pressed: true,
+            modifiers: Modifiers::default(),
+        };
+        run(&ctx, vec![press(Key::Home)], |ui| {
+            add(ui, &mut value)
+        });
+        assert_eq!(value, -10.0);
+
+        run(&ctx, vec![press(Key::End)], |ui| {
+            add(ui, &mut value)
+        });
+        assert_eq!(value, 90.0);
+    }
+
+    #[test]
+    fn precision_follows_the_range_span() {
+        let mut wide = 0.0;
+        let wide = Slider::new(&mut wide, 0.0..=1000.0);
+        let mut narrow = 0.0;
+        let narrow = Slider::new(&mut narrow, 0.0..=1.0);
+
+        let wide = wide.decimals_for_value(123.456_789);
+        let narrow =
+            narrow.decimals_for_value(0.123_456_789);
+        assert!(wide <= 1, "{}", wide);
+        assert!(narrow >= 2, "{}", narrow);
+        assert!(narrow > wide);
+    }
+
+    #[test]
+    fn integer_drag_only_visits_whole_numbers() {
+        let ctx = egui::Context::default();
+        let mut value = 0.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            ui.add(Slider::new(value, 0.0..=10.0).integer())
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+        let range = Slider::new(&mut 0.0, 0.0..=10.0)
+            .position_range(&rect);
+        let at = |t: f32| {
+            pos2(lerp(range.clone(), t), rect.center().y)
+        };
+
+        let path: Vec<_> = (1..40)
+            .map(|it| at(it as f32 * 0.0237))
+            .collect();
+        let mut seen = Vec::new();
+        for events in drag(at(0.01), &path) {
+            run(&ctx, events, |ui| add(ui, &mut value));
+            assert_eq!(value.fract(), 0.0, "{}", value);
+            seen.push(value);
+        }
+        seen.dedup();
+        assert!(seen.len() > 5, "{:?}", seen);
+    }
+
+    #[test]
+    fn value_at_the_rail_center() {
+        let ctx = egui::Context::default();
+        let rect = run(&ctx, vec![], |ui| {
+            ui.add(Slider::new(&mut 0.0, 0.0..=100.0))
+        })
+        .rect;
+        let range = Slider::new(&mut 0.0, 0.0..=100.0)
+            .position_range(&rect);
+        let center =
+            pos2(lerp(range, 0.5), rect.center().y);
+
+        let linear = Slider::new(&mut 0.0, 0.0..=100.0)
+            .value_at(center, rect);
+        assert!((linear - 50.0).abs() < 1e-6, "{}", linear);
+
+        let mut value = 1.0;
+        let mut slider =
+            Slider::new(&mut value, 1.0..=10_000.0);
+        slider.spec.logarithmic = true;
+        let log = slider.value_at(center, rect);
+        assert!((log - 100.0).abs() < 1e-6, "{}", log);
+    }
+
+    #[test]
+    fn soft_notch_picks_the_nearest_notch() {
+        let mut value = 0.0;
+        let slider = Slider::new(&mut value, 0.0..=1.0)
+            .soft_notches(20);
+
+        // notches every 0.5 point, two of them within reach
+        assert_eq!(
+            slider.soft_notch(2.9, 0.0..=10.0, 1.0),
+            Some(3.0)
+        );
+        assert_eq!(
+            slider.soft_notch(2.2, 0.0..=10.0, 1.0),
+            Some(2.0)
+        );
+        assert_eq!(
+            slider.soft_notch(2.2, 0.0..=10.0, 0.1),
+            None
+        );
+    }
+
+    #[test]
+    fn releasing_near_a_notch_lands_on_it() {
+        let ctx = egui::Context::default();
+        let mut value = 0.0;
+        let add = |ui: &mut egui::Ui, value: &mut f64| {
+            let mut slider =
+                Slider::new(value, 0.0..=100.0)
+                    .soft_notches(4);
+            slider.smart_aim = false;
+            ui.add(slider)
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+        let range = Slider::new(&mut 0.0, 0.0..=100.0)
+            .position_range(&rect);
+        let at = |t: f32, offset: f32| {
+            pos2(
+                lerp(range.clone(), t) + offset,
+                rect.center().y,
+            )
+        };
+
+        for events in drag(at(0.1, 0.0), &[at(0.5, 0.4)]) {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+        assert_eq!(value, 50.0);
+
+        for events in drag(at(0.5, 0.0), &[at(0.6, 0.0)]) {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+        assert!((value - 60.0).abs() < 0.5, "{}", value);
+    }
+
+    #[test]
+    fn power_curve_round_trips() {
+        for gamma in [0.25, 0.5, 1.0, 2.0, 3.7] {
+            let mut value = 0.0;
+            let slider =
+                Slider::new(&mut value, -20.0..=80.0)
+                    .power(gamma);
+            let range = -20.0..=80.0;
+
+            for t in [0.0, 0.1, 0.25, 0.5, 0.9, 1.0] {
+                let value = value_from_normalized(
+                    t,
+                    range.clone(),
+                    &slider.spec,
+                );
+                let expected = lerp(
+                    range.clone(),
+                    f64::powf(t, gamma),
+                );
+                assert!(
+                    (value - expected).abs() < 1e-9,
+                    "gamma {}: {} != {}",
+                    gamma,
+                    value,
+                    expected
+                );
+
+                let back = normalized_from_value(
+                    value,
+                    range.clone(),
+                    &slider.spec,
+                );
+                assert!(
+                    (back - t).abs() < 1e-9,
+                    "gamma {}: {} != {}",
+                    gamma,
+                    back,
+                    t
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn handle_shape_kinds() {
+        let rail = Rect::from_min_max(
+            pos2(10.0, 48.0),
+            pos2(210.0, 52.0),
+        );
+        let center = pos2(60.0, 50.0);
+        let stroke = egui::Stroke::none();
+        let handle = |shape, orientation| {
+            let mut value = 0.0;
+            let slider = Slider::new(&mut value, 0.0..=1.0)
+                .handle_shape(shape)
+                .orientation(orientation);
+            slider.handle(
+                center,
+                8.0,
+                &rail,
+                egui::Color32::RED,
+                stroke,
+            )
+        };
+
+        match handle(
+            HandleShape::Circle,
+            SliderOrientation::Horizontal,
+        ) {
+            epaint::Shape::Circle(circle) => {
+                assert_eq!(circle.center, center);
+                assert_eq!(circle.radius, 8.0);
+            }
+            shape => panic!("{:?}", shape),
+        }
+
+        for (orientation, beside) in [
+            (
+                SliderOrientation::Horizontal,
+                (|tip: Pos2, it: Pos2| it.y < tip.y)
+                    as fn(Pos2, Pos2) -> bool,
+            ),
+            (SliderOrientation::Vertical, |tip, it| {
+                it.x < tip.x
+            }),
+        ] {
+            match handle(HandleShape::Triangle, orientation)
+            {
+                epaint::Shape::Path(path) => {
+                    assert!(path.closed);
+                    let [tip, a, b]: [Pos2; 3] =
+                        path.points.try_into().unwrap();
+                    assert!(
+                        beside(tip, a) && beside(tip, b)
+                    );
+                }
+                shape => panic!("{:?}", shape),
+            }
+        }
+    }
+
+    #[test]
+    fn duration_drag_keeps_sub_second_precision() {
+        let ctx = egui::Context::default();
+        let mut value = Duration::ZERO;
+        let range = Duration::ZERO..=Duration::from_secs(2);
+        let add = |ui: &mut egui::Ui,
+                   value: &mut Duration| {
+            let mut slider =
+                Slider::duration(value, range.clone());
+            slider.smart_aim = false;
+            ui.add(slider)
+        };
+        let rect =
+            run(&ctx, vec![], |ui| add(ui, &mut value))
+                .rect;
+        let positions = Slider::new(&mut 0.0, 0.0..=2.0)
+            .position_range(&rect);
+        let at = |t: f32| {
+            pos2(
+                lerp(positions.clone(), t),
+                rect.center().y,
+            )
+        };
+
+        for events in drag(at(0.1), &[at(0.3)]) {
+            run(&ctx, events, |ui| add(ui, &mut value));
+        }
+
+        let secs = value.as_secs_f64();
+        assert!((secs - 0.6).abs() < 0.02, "{:?}", value);
+        assert_ne!(value.subsec_nanos(), 0);
+    }
+
+    #[test]
+    fn duration_saturates_instead_of_panicking() {
+        let mut value = Duration::ZERO;
+        let mut slider = Slider::duration(
+            &mut value,
+            Duration::ZERO..=Duration::from_secs(1),
+        );
+
+        set(&mut slider.get_set_value, f64::INFINITY);
+        assert_eq!(
+            get(&mut slider.get_set_value),
+            Duration::MAX.as_secs_f64()
+        );
+        set(&mut slider.get_set_value, -3.0);
+        drop(slider);
+        assert_eq!(value, Duration::ZERO);
+    }
+
+    #[test]
+    fn middle_click_resets_through_from_get_set() {
+        let ctx = egui::Context::default();
+        let mut value = 70.0;
+        let mut add = |ui: &mut egui::Ui| {
+            ui.add(
+                Slider::from_get_set(0.0..=100.0, |v| {
+                    if let Some(v) = v {
+                        value = v;
+                    }
+                    value
+                })
+                .reset_value(25.0),
+            )
+        };
+        let center =
+            run(&ctx, vec![], &mut add).rect.center();
+
+        for events in [
+            vec![Event::PointerMoved(center)],
+            vec![button(
+                center,
+                PointerButton::Middle,
+                true,
+            )],
+            vec![button(
+                center,
+                PointerButton::Middle,
+                false,
+            )],
+        ] {
+            run(&ctx, events, &mut add);
+        }
+
+        assert_eq!(value, 25.0);
+    }
+}
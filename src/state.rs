@@ -1,34 +1,462 @@
+use std::{
+    collections::VecDeque, ops::RangeInclusive,
+    time::Duration,
+};
+
 use egui::TextureHandle;
-use serde::{Deserialize, Serialize};
 
 use crate::{
-    horizontal_state::HorizontalState,
-    vertical_state::VerticalState,
+    calculate_distance, horizontal_state::HorizontalState,
+    vertical_state::VerticalState, Integrator, Now,
+    DEFAULT_GRAVITY, DEFAULT_RESTITUTION,
 };
 
-#[derive(Default, Serialize, Deserialize)]
+/// daerah dengan gravitasi berbeda, misal air di bawah
+/// garis tertentu yang membuat bola mengapung
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct GravityZone {
+    pub y: RangeInclusive<f32>,
+    /// pengali gravitasi, nilai negatif membuat bola naik
+    pub gravity_multiplier: f64,
+}
+
+/// versi format simpanan `GLBBState` sekarang
+pub const STATE_VERSION: u32 = 1;
+
+/// batas default `dt` dalam satu langkah simulasi
+pub const DEFAULT_MAX_DT: Duration =
+    Duration::from_millis(100);
+
+fn default_max_dt() -> Duration {
+    DEFAULT_MAX_DT
+}
+
+fn default_floor_friction() -> f64 {
+    1.0
+}
+
+fn default_perspective() -> bool {
+    true
+}
+
+/// radius bola saat di lantai jika tidak diatur
+pub const DEFAULT_RADIUS: f32 = 30.0;
+
+/// jarak lantai dari tepi bawah layar, supaya bola tidak
+/// menimpa garis bingkai
+pub const SCREEN_FLOOR_OFFSET: f32 = 2.0;
+
+/// titik (0,0) dari posisi bola di layar
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum CoordinateOrigin {
+    /// pojok kiri bawah, y ke atas
+    #[default]
+    BottomLeft,
+    /// pojok kiri atas, y ke bawah seperti koordinat layar.
+    ///
+    /// fisika tetap menganggap y = 0 sebagai lantai, jadi
+    /// bola akan "jatuh" ke tepi atas
+    TopLeft,
+}
+
+/// jenis peluncuran bola
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum LaunchKind {
+    Left,
+    Right,
+    Fall,
+    /// dilempar miring dengan sudut [`Launch::angle`]
+    Projectile,
+}
+
+/// sisi area bermain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wall {
+    Left,
+    Right,
+    Floor,
+    Ceiling,
+}
+
+/// apa yang terjadi saat bola menyentuh sebuah sisi
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum WallBehavior {
+    /// memantul dan berbalik arah
+    #[default]
+    Bounce,
+    /// tertahan di sisi, kecepatan ke arah sisi itu hilang
+    Clamp,
+    /// keluar lalu muncul di sisi seberang
+    Wrap,
+    /// seluruh gerakan bola berhenti
+    Stop,
+}
+
+/// kondisi awal dari peluncuran bola
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Launch {
+    pub kind: LaunchKind,
+    pub pos: egui::Pos2,
+    pub velocity: f64,
+    pub acceleration: f64,
+    /// sudut lemparan dalam derajat, hanya untuk
+    /// [`LaunchKind::Projectile`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub angle: f64,
+}
+
+/// pengaturan percobaan tanpa posisi dan gerakan bola saat
+/// ini, untuk dibagikan
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Config {
+    pub velocity: f64,
+    pub acceleration: f64,
+    pub gravity: f64,
+    pub restitution: f64,
+    pub floor_friction: f64,
+    pub radius: f32,
+    pub min_radius: f32,
+}
+
+/// kecepatan naik terkecil setelah memantul di atas
+/// rintangan, di bawahnya bola dianggap diam di sana
+const OBSTACLE_REST_SPEED: f64 = 10.0;
+
+/// jumlah sampel terbanyak di setiap riwayat jika tidak
+/// diatur, lihat [`GLBBState::history_capacity`]
+pub const DEFAULT_HISTORY_CAPACITY: usize = 10_000;
+
+fn default_history_capacity() -> usize {
+    DEFAULT_HISTORY_CAPACITY
+}
+
+/// tambahkan `value` ke belakang, buang sampel paling lama
+/// jika sudah lebih dari `capacity`
+fn push_history<T>(
+    buffer: &mut VecDeque<T>,
+    capacity: usize,
+    value: T,
+) {
+    buffer.push_back(value);
+    while buffer.len() > capacity {
+        buffer.pop_front();
+    }
+}
+
+/// energi bola per satuan massa pada satu saat
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EnergySample {
+    /// waktu simulasi dalam detik
+    pub time: f64,
+    pub kinetic: f64,
+    /// relatif terhadap lantai, tanpa memperhitungkan
+    /// [`GravityZone`]
+    pub potential: f64,
+}
+
+impl EnergySample {
+    /// energi mekanik
+    pub fn total(&self) -> f64 {
+        self.kinetic + self.potential
+    }
+}
+
+/// sumbu gerak bola
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// besaran fisika bola pada satu saat, positif ke kanan
+/// dan ke atas
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub pos: egui::Pos2,
+    pub vx: f64,
+    pub vy: f64,
+    pub ax: f64,
+    pub ay: f64,
+    /// lama simulasi berjalan sejak peluncuran terakhir
+    pub sim_time: Duration,
+}
+
+impl Snapshot {
+    /// besar kecepatan
+    pub fn speed(&self) -> f64 {
+        self.vx.hypot(self.vy)
+    }
+
+    /// besar percepatan
+    pub fn accel(&self) -> f64 {
+        self.ax.hypot(self.ay)
+    }
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct GLBBState {
+    /// versi format simpanan, simpanan lama tanpa versi
+    /// dianggap versi 0
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: u32,
+
     pub pos: egui::Pos2,
     pub original_radius: f32,
+    /// radius terkecil bola walaupun sedang tinggi, supaya
+    /// tetap terlihat dan bisa diklik
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_radius: f32,
     pub size: egui::Vec2,
 
     pub horizontal: HorizontalState,
     pub vertical: VerticalState,
 
-    #[serde(skip)]
+    /// kosong berarti gravitasi seragam
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zones: Vec<GravityZone>,
+
+    /// batas `dt` dalam satu langkah, supaya bola tidak
+    /// melompat jauh setelah aplikasi macet atau diminimize
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_max_dt")
+    )]
+    pub max_dt: Duration,
+
+    /// pengali kecepatan horizontal setiap bola memantul di
+    /// lantai, 1.0 berarti tanpa gesekan
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_floor_friction")
+    )]
+    pub floor_friction: f64,
+
+    /// batas besar kecepatan bola, kecepatan yang lebih
+    /// besar diperkecil tanpa mengubah arahnya
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_speed: Option<f64>,
+
+    /// jika diisi, bola dipindah ke titik ini setiap
+    /// diluncurkan sehingga percobaan bisa diulang dari
+    /// posisi yang sama
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub launch_origin: Option<egui::Pos2>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    last_launch: Option<Launch>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub origin: CoordinateOrigin,
+
+    /// bola tidak kehilangan kecepatan, lihat
+    /// [`Self::billiard_mode`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    billiard: bool,
+    /// gravitasi, restitusi dan gesekan lantai sebelum mode
+    /// biliar dinyalakan, dikembalikan saat dimatikan
+    #[cfg_attr(feature = "serde", serde(default))]
+    before_billiard: Option<(f64, f64, f64)>,
+
+    /// rintangan diam yang memantulkan bola, dalam koordinat
+    /// yang sama dengan `pos`: y ke atas dari lantai
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub obstacles: Vec<egui::Rect>,
+    /// bola sedang diam di atas rintangan
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_obstacle: bool,
+
+    /// skala kecepatan dan percepatan ikut berubah saat
+    /// ukuran area berubah, lihat [`Self::rescale_for_size`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scale_with_size: bool,
+    /// bola mengecil saat naik, lihat [`Self::perspective`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_perspective")
+    )]
+    perspective: bool,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub circle_texture: Option<TextureHandle>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frame: Now,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frame_dt: Duration,
+    /// jam simulasi, hanya maju saat bola bergerak
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip, default = "Now::manual")
+    )]
+    sim_time: Now,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    paused: bool,
+    /// ada perubahan sejak [`Self::take_dirty`] terakhir
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dirty: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    energy_history: VecDeque<EnergySample>,
+
+    /// jarak waktu antar titik jejak, `None` berarti tidak
+    /// merekam jejak
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ticker_interval: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ticker_marks: VecDeque<egui::Pos2>,
+    /// jumlah sampel terbanyak di setiap riwayat (jejak
+    /// ticker, bayangan, jejak bola dan energi), sampel
+    /// paling lama dibuang lebih dulu
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_history_capacity")
+    )]
+    pub history_capacity: usize,
+    /// posisi lama untuk bayangan bola, paling lama di depan
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ghosts: VecDeque<egui::Pos2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ghost_frame: u32,
+    /// posisi bola di frame-frame terakhir, paling lama di
+    /// depan
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trail: VecDeque<egui::Pos2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_tick: Duration,
+}
+
+impl Default for GLBBState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            pos: Default::default(),
+            original_radius: DEFAULT_RADIUS,
+            min_radius: Default::default(),
+            size: Default::default(),
+            horizontal: Default::default(),
+            vertical: Default::default(),
+            zones: Default::default(),
+            max_dt: default_max_dt(),
+            floor_friction: default_floor_friction(),
+            max_speed: Default::default(),
+            launch_origin: Default::default(),
+            last_launch: Default::default(),
+            origin: Default::default(),
+            billiard: Default::default(),
+            before_billiard: Default::default(),
+            obstacles: Default::default(),
+            on_obstacle: Default::default(),
+            scale_with_size: Default::default(),
+            perspective: default_perspective(),
+            circle_texture: Default::default(),
+            frame: Default::default(),
+            frame_dt: Default::default(),
+            sim_time: Now::manual(),
+            paused: Default::default(),
+            dirty: Default::default(),
+            energy_history: Default::default(),
+            ticker_interval: Default::default(),
+            ticker_marks: Default::default(),
+            history_capacity: default_history_capacity(),
+            ghosts: Default::default(),
+            ghost_frame: Default::default(),
+            trail: Default::default(),
+            next_tick: Default::default(),
+        }
+    }
 }
 
 impl GLBBState {
+    /// state dengan radius bola `original_radius`, nilai
+    /// yang tidak positif diganti [`DEFAULT_RADIUS`]
+    pub fn new(original_radius: f32) -> Self {
+        Self::default().with_radius(original_radius)
+    }
+
+    /// ubah state dari simpanan versi lama menjadi versi
+    /// sekarang
+    pub fn migrate(&mut self) {
+        if self.version < 1 {
+            // versi 0 belum menyimpan kecepatan awal dan belum
+            // menolak radius yang tidak positif. field baru
+            // lainnya sudah diisi nilai default oleh serde
+            self.horizontal.restore_launch_velocity();
+            if !self
+                .set_original_radius(self.original_radius)
+            {
+                self.original_radius = DEFAULT_RADIUS;
+            }
+        }
+
+        self.version = STATE_VERSION;
+    }
+
     /// translasi posisi dari bola ke layar yang berada di
     /// rect
     pub fn pos_to_screen(
         &self,
         rect: egui::Rect,
     ) -> egui::Pos2 {
-        let radius = self.radius_size();
-        let x = self.pos.x + rect.min.x + radius.x;
-        let y = rect.max.y - radius.y - self.pos.y - 2.0;
+        self.point_to_screen(self.pos, rect)
+    }
+
+    /// titik tengah bola di layar jika bola berada di `pos`
+    pub fn point_to_screen(
+        &self,
+        pos: egui::Pos2,
+        rect: egui::Rect,
+    ) -> egui::Pos2 {
+        self.point_to_screen_with(
+            pos,
+            self.radius_at(pos.y),
+            rect,
+        )
+    }
+
+    fn point_to_screen_with(
+        &self,
+        pos: egui::Pos2,
+        radius: f32,
+        rect: egui::Rect,
+    ) -> egui::Pos2 {
+        let x = pos.x + rect.min.x + radius;
+        let y = match self.origin {
+            CoordinateOrigin::BottomLeft => {
+                rect.max.y
+                    - radius
+                    - pos.y
+                    - SCREEN_FLOOR_OFFSET
+            }
+            CoordinateOrigin::TopLeft => {
+                rect.min.y + radius + pos.y
+            }
+        };
 
         egui::pos2(x, y)
     }
@@ -41,16 +469,1096 @@ impl GLBBState {
     ) -> egui::Pos2 {
         let radius = self.radius_size();
         let x = pos.x - rect.min.x - radius.x;
-        let y = rect.max.y - pos.y - radius.y;
+        let y = match self.origin {
+            CoordinateOrigin::BottomLeft => {
+                rect.max.y
+                    - pos.y
+                    - radius.y
+                    - SCREEN_FLOOR_OFFSET
+            }
+            CoordinateOrigin::TopLeft => {
+                pos.y - rect.min.y - radius.y
+            }
+        };
 
         egui::pos2(x, y)
     }
 
+    /// kotak yang membungkus bola di layar yang berada di
+    /// rect
+    pub fn screen_rect(
+        &self,
+        rect: egui::Rect,
+    ) -> egui::Rect {
+        egui::Rect::from_center_size(
+            self.pos_to_screen(rect),
+            self.radius_size() * 2.0,
+        )
+    }
+
+    /// daerah di layar tempat titik tengah bola bisa berada,
+    /// yaitu `0..=pos_max()` dengan radius sekarang
+    pub fn bounds_screen_rect(
+        &self,
+        rect: egui::Rect,
+    ) -> egui::Rect {
+        let radius = self.radius();
+        egui::Rect::from_two_pos(
+            self.point_to_screen_with(
+                egui::Pos2::ZERO,
+                radius,
+                rect,
+            ),
+            self.point_to_screen_with(
+                self.pos_max().to_pos2(),
+                radius,
+                rect,
+            ),
+        )
+    }
+
+    /// cek apakah titik layar `pos` berada di atas bola,
+    /// bukan di latar
+    pub fn contains_screen_point(
+        &self,
+        rect: egui::Rect,
+        pos: egui::Pos2,
+    ) -> bool {
+        self.pos_to_screen(rect).distance(pos)
+            <= self.radius()
+    }
+
+    /// total jarak horizontal yang sudah ditempuh bola
+    pub fn horizontal_distance_traveled(&self) -> f64 {
+        self.horizontal.distance_traveled()
+    }
+
+    /// perkiraan waktu sampai bola menyentuh lantai
+    pub fn time_to_floor(&self) -> Option<Duration> {
+        self.vertical.time_to_floor(
+            self.pos.y as f64,
+            self.vertical.accel * self.gravity_scale(),
+        )
+    }
+
+    /// sisa energi bola dibanding saat terakhir dijatuhkan,
+    /// `None` jika peluncuran terakhir bukan jatuh
+    pub fn energy_fraction(&self) -> Option<f64> {
+        let launch = self
+            .last_launch
+            .as_ref()
+            .filter(|it| it.kind == LaunchKind::Fall)?;
+
+        Some(self.vertical.energy_fraction(
+            self.pos.y as f64,
+            launch.pos.y as f64,
+        ))
+    }
+
+    /// perkiraan waktu sampai bola mencapai posisi x
+    /// `target_x`
+    pub fn time_to_reach_x(
+        &self,
+        target_x: f32,
+    ) -> Option<Duration> {
+        self.horizontal.time_to_reach(
+            self.pos.x as f64,
+            target_x as f64,
+        )
+    }
+
+    /// waktu dan ketinggian titik tertinggi bola
+    pub fn apex(&self) -> (Duration, f64) {
+        self.vertical.apex(
+            self.pos.y as f64,
+            self.vertical.accel * self.gravity_scale(),
+        )
+    }
+
+    /// perkiraan posisi bola setiap `dt` mulai dari sekarang
+    /// dengan kecepatan dan percepatan saat ini, tanpa
+    /// pantulan. bola tidak menembus lantai dan perlambatan
+    /// horizontal berhenti saat kecepatannya habis
+    pub fn predict_trajectory(
+        &self,
+        steps: usize,
+        dt: Duration,
+    ) -> Vec<egui::Pos2> {
+        let snapshot = self.snapshot();
+        let dt = dt.as_secs_f64();
+        let stop_time = if snapshot.vx * snapshot.ax < 0.0 {
+            -snapshot.vx / snapshot.ax
+        } else {
+            f64::INFINITY
+        };
+
+        (0..steps)
+            .map(|i| {
+                let t = i as f64 * dt;
+                let x = calculate_distance(
+                    snapshot.vx,
+                    snapshot.ax,
+                    t.min(stop_time),
+                );
+                let y = calculate_distance(
+                    snapshot.vy,
+                    snapshot.ay,
+                    t,
+                );
+                egui::pos2(
+                    self.pos.x + x as f32,
+                    (self.pos.y + y as f32).max(0.0),
+                )
+            })
+            .collect()
+    }
+
+    /// [`Self::predict_trajectory`] dalam koordinat layar,
+    /// siap digambar sebagai garis
+    pub fn trajectory_screen_points(
+        &self,
+        rect: egui::Rect,
+        steps: usize,
+        dt: Duration,
+    ) -> Vec<egui::Pos2> {
+        self.predict_trajectory(steps, dt)
+            .into_iter()
+            .map(|it| self.point_to_screen(it, rect))
+            .collect()
+    }
+
+    /// gerakkan bola ke kiri
+    pub fn play_left(&mut self) {
+        self.launch(self.current_launch(LaunchKind::Left));
+    }
+
+    /// gerakkan bola ke kanan
+    pub fn play_right(&mut self) {
+        self.launch(self.current_launch(LaunchKind::Right));
+    }
+
+    /// jatuhkan bola dari posisi sekarang. tanpa gravitasi,
+    /// misal di [`Self::billiard_mode`], bola tidak akan
+    /// pernah bergerak sehingga tidak ada yang dilakukan
+    pub fn fall(&mut self) {
+        if self.vertical.gravity * self.gravity_scale()
+            <= 0.0
+        {
+            return;
+        }
+        self.launch(self.current_launch(LaunchKind::Fall));
+    }
+
+    /// lempar bola dengan kecepatan `speed` dan sudut
+    /// `angle_deg` derajat dari lantai, 90 berarti lurus ke
+    /// atas. arah horizontal bergerak dengan kecepatan tetap
+    pub fn launch_projectile(
+        &mut self,
+        speed: f64,
+        angle_deg: f64,
+    ) {
+        self.launch(Launch {
+            velocity: speed,
+            angle: angle_deg,
+            ..self.current_launch(LaunchKind::Projectile)
+        });
+    }
+
+    /// perkiraan lama bola di udara sejak lemparan miring
+    /// terakhir sampai kembali ke ketinggian awal. nol jika
+    /// peluncuran terakhir bukan lemparan miring
+    pub fn flight_time(&self) -> Duration {
+        let velocity_up = match &self.last_launch {
+            Some(launch)
+                if launch.kind
+                    == LaunchKind::Projectile =>
+            {
+                launch.velocity
+                    * launch.angle.to_radians().sin()
+            }
+            _ => return Duration::ZERO,
+        };
+        let gravity = self.vertical.gravity;
+        if velocity_up <= 0.0 || gravity <= 0.0 {
+            return Duration::ZERO;
+        }
+
+        Duration::try_from_secs_f64(
+            2.0 * velocity_up / gravity,
+        )
+        .unwrap_or(Duration::MAX)
+    }
+
+    fn current_launch(&self, kind: LaunchKind) -> Launch {
+        Launch {
+            kind,
+            pos: self.launch_origin.unwrap_or(self.pos),
+            velocity: self.horizontal.launch_velocity(),
+            acceleration: self.horizontal.acceleration,
+            angle: 0.0,
+        }
+    }
+
+    fn launch(&mut self, launch: Launch) {
+        let kind = launch.kind;
+        let (sin, cos) =
+            launch.angle.to_radians().sin_cos();
+        let speed = launch.velocity;
+        self.pos = launch.pos;
+        self.last_launch = Some(launch);
+        self.sim_time.reset();
+        self.paused = false;
+        self.dirty = true;
+        self.frame.reset();
+        self.ticker_marks.clear();
+        self.ghosts.clear();
+        self.trail.clear();
+        self.next_tick = Duration::ZERO;
+        self.record_ticker();
+        self.energy_history.clear();
+
+        let velocity = self.horizontal.launch_velocity();
+        match kind {
+            LaunchKind::Left if self.billiard => {
+                self.horizontal.play_constant(velocity, -1)
+            }
+            LaunchKind::Right if self.billiard => {
+                self.horizontal.play_constant(velocity, 1)
+            }
+            LaunchKind::Left => self.horizontal.play_left(),
+            LaunchKind::Right => {
+                self.horizontal.play_right()
+            }
+            LaunchKind::Fall => self.vertical.fall(),
+            LaunchKind::Projectile => {
+                let velocity_x = speed * cos;
+                if velocity_x.abs() > f64::EPSILON {
+                    self.horizontal.play_constant(
+                        velocity_x,
+                        velocity_x.signum() as i8,
+                    );
+                } else {
+                    self.horizontal.stop();
+                }
+                self.vertical.throw(speed * sin);
+            }
+        }
+    }
+
+    /// kondisi peluncuran terakhir
+    pub fn last_launch(&self) -> Option<&Launch> {
+        self.last_launch.as_ref()
+    }
+
+    /// ulangi peluncuran terakhir dari posisi awalnya,
+    /// tidak melakukan apa-apa jika belum pernah diluncurkan
+    pub fn replay_launch(&mut self) {
+        if let Some(launch) = self.last_launch.clone() {
+            self.horizontal.stop();
+            self.vertical.stop();
+
+            self.pos = launch.pos;
+            // kecepatan lemparan miring bukan kecepatan
+            // horizontal yang diisi pengguna
+            if launch.kind != LaunchKind::Projectile {
+                self.horizontal
+                    .set_velocity(launch.velocity);
+            }
+            self.horizontal.acceleration =
+                launch.acceleration;
+            self.launch(launch);
+        }
+    }
+
+    /// tambahkan kecepatan `delta_v` ke bola seketika, y
+    /// positif berarti ke atas. bola yang diam mulai bergerak
+    pub fn apply_impulse(&mut self, delta_v: egui::Vec2) {
+        self.dirty = true;
+        if !self.is_play() {
+            self.frame.reset();
+        }
+        if delta_v.x != 0.0 {
+            self.horizontal.set_velocity_x(
+                self.horizontal.velocity_x()
+                    + delta_v.x as f64,
+            );
+        }
+
+        if delta_v.y != 0.0 {
+            let velocity_up = if self.vertical.is_play() {
+                self.vertical.velocity_up()
+            } else {
+                0.0
+            };
+            self.vertical
+                .throw(velocity_up + delta_v.y as f64);
+        }
+    }
+
+    /// ambil pengaturan percobaan sekarang
+    pub fn export_config(&self) -> Config {
+        Config {
+            velocity: self.horizontal.launch_velocity(),
+            acceleration: self.horizontal.acceleration,
+            gravity: self.vertical.gravity,
+            restitution: self.vertical.restitution,
+            floor_friction: self.floor_friction,
+            radius: self.original_radius,
+            min_radius: self.min_radius,
+        }
+    }
+
+    /// pakai pengaturan percobaan, bola dihentikan dan
+    /// dikembalikan ke posisi awal
+    pub fn apply_config(&mut self, config: Config) {
+        self.reset();
+        self.pos = egui::Pos2::ZERO;
+
+        self.horizontal.set_velocity(config.velocity);
+        self.horizontal.acceleration = config.acceleration;
+        self.vertical.gravity = config.gravity;
+        self.vertical.restitution = config.restitution;
+        self.floor_friction = config.floor_friction;
+        self.set_original_radius(config.radius);
+        self.min_radius = config.min_radius;
+    }
+
+    /// atur radius bola saat di lantai, lihat
+    /// [`Self::set_original_radius`]
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.set_original_radius(radius);
+        self
+    }
+
+    /// atur radius bola saat di lantai. nilai yang tidak
+    /// positif ditolak, radius tidak berubah dan hasilnya
+    /// `false`
+    pub fn set_original_radius(
+        &mut self,
+        radius: f32,
+    ) -> bool {
+        let valid = radius.is_finite() && radius > 0.0;
+        if valid {
+            self.original_radius = radius;
+            self.dirty = true;
+        }
+        valid
+    }
+
+    /// mode biliar: tanpa gravitasi, gesekan dan perlambatan,
+    /// setiap pantulan hanya membalik arah sehingga besar
+    /// kecepatan bola selalu tetap
+    pub fn billiard_mode(&mut self, enabled: bool) {
+        let was_enabled = self.billiard;
+        self.billiard = enabled;
+        self.dirty = true;
+
+        if enabled && !was_enabled {
+            self.before_billiard = Some((
+                self.vertical.gravity,
+                self.vertical.restitution,
+                self.floor_friction,
+            ));
+        }
+
+        let (gravity, restitution, floor_friction) =
+            if enabled {
+                (0.0, 1.0, default_floor_friction())
+            } else if let Some(it) =
+                self.before_billiard.take()
+            {
+                it
+            } else if was_enabled {
+                // disimpan sebelum pengaturan lama diingat
+                (
+                    DEFAULT_GRAVITY,
+                    DEFAULT_RESTITUTION,
+                    default_floor_friction(),
+                )
+            } else {
+                (
+                    self.vertical.gravity,
+                    self.vertical.restitution,
+                    self.floor_friction,
+                )
+            };
+
+        let vertical = &mut self.vertical;
+        vertical.gravity = gravity;
+        vertical.restitution = restitution;
+        if vertical.is_play() {
+            vertical.accel = vertical.gravity;
+        }
+        self.floor_friction = floor_friction;
+
+        // lanjutkan gerakan horizontal dengan aturan baru
+        let velocity_x = self.horizontal.velocity_x();
+        if velocity_x != 0.0 {
+            if enabled {
+                self.horizontal.play_constant(
+                    velocity_x.abs(),
+                    if velocity_x < 0.0 { -1 } else { 1 },
+                );
+            } else {
+                // lanjutkan dari kecepatan sekarang, hanya
+                // mulai melambat lagi
+                self.horizontal.set_constant(false);
+                self.horizontal.set_velocity_x(velocity_x);
+            }
+        }
+    }
+
+    pub fn is_billiard_mode(&self) -> bool {
+        self.billiard
+    }
+
+    /// bola yang keluar dari dinding kiri atau kanan muncul
+    /// di dinding seberang dengan kecepatan yang sama
+    pub fn wrap_edges(&mut self, enabled: bool) {
+        let behavior = if enabled {
+            WallBehavior::Wrap
+        } else {
+            WallBehavior::Bounce
+        };
+        self.set_wall(Wall::Left, behavior);
+        self.set_wall(Wall::Right, behavior);
+    }
+
+    pub fn is_wrap_edges(&self) -> bool {
+        self.wall(Wall::Left) == WallBehavior::Wrap
+            && self.wall(Wall::Right) == WallBehavior::Wrap
+    }
+
+    /// atur perilaku bola di sisi `wall`
+    pub fn set_wall(
+        &mut self,
+        wall: Wall,
+        behavior: WallBehavior,
+    ) {
+        match wall {
+            Wall::Left => self.horizontal.left = behavior,
+            Wall::Right => self.horizontal.right = behavior,
+            Wall::Floor => self.vertical.floor = behavior,
+            Wall::Ceiling => {
+                self.vertical.ceiling = behavior
+            }
+        }
+        self.dirty = true;
+    }
+
+    pub fn wall(&self, wall: Wall) -> WallBehavior {
+        match wall {
+            Wall::Left => self.horizontal.left,
+            Wall::Right => self.horizontal.right,
+            Wall::Floor => self.vertical.floor,
+            Wall::Ceiling => self.vertical.ceiling,
+        }
+    }
+
+    /// hentikan seluruh gerakan jika bola baru saja berhenti
+    /// di sisi dengan [`WallBehavior::Stop`].
+    /// `stopped_at_side` dari [`HorizontalState::step`]
+    fn stop_at_walls(
+        &mut self,
+        stopped_at_side: bool,
+        vertical_was_play: bool,
+        max: egui::Vec2,
+    ) {
+        let stopped_at = |wall: Wall, at_wall: bool| {
+            at_wall && self.wall(wall) == WallBehavior::Stop
+        };
+        let stopped_at_floor_or_ceiling = vertical_was_play
+            && !self.vertical.is_play()
+            && (stopped_at(Wall::Floor, self.pos.y <= 0.0)
+                || stopped_at(
+                    Wall::Ceiling,
+                    self.pos.y >= max.y,
+                ));
+
+        if stopped_at_side {
+            self.vertical.stop();
+        }
+        if stopped_at_floor_or_ceiling {
+            self.horizontal.stop();
+        }
+    }
+
+    /// atur apakah bola mengecil saat naik. jika tidak,
+    /// radius selalu `original_radius`
+    pub fn perspective(&mut self, enabled: bool) {
+        self.perspective = enabled;
+        self.dirty = true;
+    }
+
+    pub fn has_perspective(&self) -> bool {
+        self.perspective
+    }
+
+    /// pilih cara menghitung gerak vertikal bola
+    pub fn integrator(&mut self, integrator: Integrator) {
+        self.vertical.integrator = integrator;
+        self.dirty = true;
+    }
+
+    /// hentikan sementara tanpa menghapus arah, kecepatan dan
+    /// sisa durasi gerakan
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.dirty = true;
+    }
+
+    /// lanjutkan gerakan setelah [`Self::pause`] tepat dari
+    /// keadaan saat dihentikan
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.dirty = true;
+            // waktu selama berhenti tidak ikut dihitung
+            self.frame.reset();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// tandai ada perubahan, untuk perubahan langsung pada
+    /// field seperti `pos`
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// cek apakah ada perubahan sejak pemanggilan terakhir,
+    /// sekaligus hapus tandanya
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// bola diam atau dihentikan sementara, layar tidak perlu
+    /// digambar ulang terus menerus
+    pub fn is_at_rest(&self) -> bool {
+        !self.is_play() || self.paused
+    }
+
     /// cek apakah bola sedang bergerak
     pub fn is_play(&self) -> bool {
         self.horizontal.is_play() || self.vertical.is_play()
     }
 
+    /// gerakkan bola sesuai waktu yang sudah berlalu sejak
+    /// gerakan terakhir.
+    ///
+    /// mengembalikan kecepatan tumbukan jika bola memantul
+    /// di lantai
+    pub fn mv(&mut self) -> Option<f64> {
+        self.step(self.frame.elapsed())
+    }
+
+    /// posisi, kecepatan dan percepatan bola sekarang
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pos: self.pos,
+            vx: self.horizontal.velocity_x(),
+            vy: if self.vertical.is_play() {
+                self.vertical.velocity_up()
+            } else {
+                0.0
+            },
+            ax: self.horizontal.acceleration_x(),
+            ay: self
+                .vertical
+                .accel_up(self.gravity_scale()),
+            sim_time: self.sim_time.elapsed(),
+        }
+    }
+
+    /// kecepatan bola, positif ke kanan dan ke atas
+    pub fn velocity_vector(&self) -> egui::Vec2 {
+        let snapshot = self.snapshot();
+        egui::vec2(snapshot.vx as f32, snapshot.vy as f32)
+    }
+
+    /// sumbu dengan kecepatan terbesar, `None` jika bola diam
+    pub fn dominant_axis(&self) -> Option<Axis> {
+        let velocity = self.velocity_vector();
+        if self.is_at_rest() || velocity == egui::Vec2::ZERO
+        {
+            None
+        } else if velocity.x.abs() >= velocity.y.abs() {
+            Some(Axis::Horizontal)
+        } else {
+            Some(Axis::Vertical)
+        }
+    }
+
+    /// kecepatan bola di layar dalam pixel per `dt`, misal
+    /// untuk motion blur. y ke bawah seperti koordinat layar
+    pub fn screen_velocity(
+        &self,
+        dt: Duration,
+    ) -> egui::Vec2 {
+        let snapshot = self.snapshot();
+        let dt = dt.as_secs_f64();
+        let vy = match self.origin {
+            CoordinateOrigin::BottomLeft => -snapshot.vy,
+            CoordinateOrigin::TopLeft => snapshot.vy,
+        };
+
+        egui::vec2(
+            (snapshot.vx * dt) as f32,
+            (vy * dt) as f32,
+        )
+    }
+
+    /// lama simulasi berjalan sejak peluncuran terakhir
+    pub fn sim_time(&self) -> Duration {
+        self.sim_time.elapsed()
+    }
+
+    /// waktu sejak [`Self::mv`] atau [`Self::step`] terakhir,
+    /// atau sejak bola diluncurkan jika lebih baru. dipakai
+    /// sebagai `dt` frame berikutnya
+    pub fn since_last_step(&self) -> Duration {
+        self.frame.elapsed()
+    }
+
+    /// ganti jam yang mengukur [`Self::since_last_step`],
+    /// misal [`Now::with_elapsed`] supaya frame berikutnya
+    /// melangkah tepat sejauh itu tanpa menunggu
+    pub fn set_frame_clock(&mut self, clock: Now) {
+        self.frame = clock;
+    }
+
+    /// `dt` dari pemanggilan [`Self::mv`] atau [`Self::step`]
+    /// terakhir, sebelum dibatasi `max_dt`
+    pub fn frame_dt(&self) -> Duration {
+        self.frame_dt
+    }
+
+    /// gerakkan bola sejauh `dt` tanpa melihat jam, berguna
+    /// untuk simulasi tanpa layar.
+    ///
+    /// mengembalikan kecepatan tumbukan jika bola memantul
+    /// di lantai
+    pub fn step(&mut self, dt: Duration) -> Option<f64> {
+        self.frame_dt = dt;
+        self.frame.reset();
+        if self.paused {
+            return None;
+        }
+        let dt = dt.min(self.max_dt);
+        if self.is_play() {
+            self.sim_time.advance(dt);
+            self.dirty = true;
+        }
+        self.clamp();
+        let max = self.pos_max();
+        let gravity_scale = self.gravity_scale();
+        let vertical_was_play = self.vertical.is_play();
+
+        let stopped_at_side = self.horizontal.step(
+            &mut self.pos.x,
+            0.0..=max.x,
+            dt,
+        );
+        let bounce = self.vertical.step(
+            &mut self.pos.y,
+            max.y,
+            dt,
+            gravity_scale,
+        );
+
+        self.stop_at_walls(
+            stopped_at_side,
+            vertical_was_play,
+            max,
+        );
+        self.limit_speed();
+        if bounce.is_some() {
+            self.floor_bounce();
+        }
+
+        self.collide_obstacles();
+        self.clamp();
+        self.record_ticker();
+        self.record_energy();
+
+        bounce
+    }
+
+    /// rekam posisi bola setiap `ticker_interval` waktu
+    /// simulasi
+    fn record_ticker(&mut self) {
+        let interval = match self.ticker_interval {
+            Some(it) if !it.is_zero() => it,
+            _ => return,
+        };
+
+        while self.next_tick <= self.sim_time.elapsed() {
+            push_history(
+                &mut self.ticker_marks,
+                self.history_capacity,
+                self.pos,
+            );
+            self.next_tick += interval;
+        }
+    }
+
+    /// energi bola sekarang per satuan massa
+    pub fn energy(&self) -> EnergySample {
+        let snapshot = self.snapshot();
+        let gravity =
+            self.vertical.gravity * self.gravity_scale();
+
+        EnergySample {
+            time: self.sim_time.elapsed_secs(),
+            kinetic: 0.5 * snapshot.speed().powi(2),
+            potential: gravity * self.pos.y.max(0.0) as f64,
+        }
+    }
+
+    /// simpan energi sekarang selama bola bergerak
+    fn record_energy(&mut self) {
+        if !self.is_play() {
+            return;
+        }
+
+        let sample = self.energy();
+        push_history(
+            &mut self.energy_history,
+            self.history_capacity,
+            sample,
+        );
+    }
+
+    /// semua sampel energi sejak peluncuran terakhir
+    pub fn energy_history(
+        &self,
+    ) -> impl Iterator<Item = &EnergySample> + '_ {
+        self.energy_history.iter()
+    }
+
+    /// titik `[waktu, energi kinetik]` untuk plot
+    pub fn kinetic_energy_series(&self) -> Vec<[f64; 2]> {
+        self.energy_series(|it| it.kinetic)
+    }
+
+    /// titik `[waktu, energi potensial]` untuk plot
+    pub fn potential_energy_series(&self) -> Vec<[f64; 2]> {
+        self.energy_series(|it| it.potential)
+    }
+
+    /// titik `[waktu, energi mekanik]` untuk plot
+    pub fn total_energy_series(&self) -> Vec<[f64; 2]> {
+        self.energy_series(EnergySample::total)
+    }
+
+    fn energy_series(
+        &self,
+        value: impl Fn(&EnergySample) -> f64,
+    ) -> Vec<[f64; 2]> {
+        self.energy_history
+            .iter()
+            .map(|it| [it.time, value(it)])
+            .collect()
+    }
+
+    /// simpan posisi bola setiap `stride` frame selama
+    /// bergerak, paling banyak `count` posisi
+    pub(crate) fn record_ghost(
+        &mut self,
+        count: usize,
+        stride: u32,
+    ) {
+        if !self.is_play() || self.paused {
+            return;
+        }
+
+        self.ghost_frame += 1;
+        if self.ghost_frame < stride.max(1) {
+            return;
+        }
+        self.ghost_frame = 0;
+
+        push_history(
+            &mut self.ghosts,
+            count.min(self.history_capacity),
+            self.pos,
+        );
+    }
+
+    /// simpan posisi bola setiap frame selama bergerak,
+    /// paling banyak `length` posisi
+    pub(crate) fn record_trail(&mut self, length: usize) {
+        if !self.is_play() || self.paused {
+            return;
+        }
+
+        push_history(
+            &mut self.trail,
+            length.min(self.history_capacity),
+            self.pos,
+        );
+    }
+
+    /// jejak bola, paling lama di depan
+    pub fn trail(&self) -> &VecDeque<egui::Pos2> {
+        &self.trail
+    }
+
+    /// posisi bayangan bola, paling lama di depan
+    pub fn ghosts(&self) -> &VecDeque<egui::Pos2> {
+        &self.ghosts
+    }
+
+    /// titik jejak yang sudah direkam sejak peluncuran
+    /// terakhir
+    pub fn ticker_marks(&self) -> &VecDeque<egui::Pos2> {
+        &self.ticker_marks
+    }
+
+    /// hentikan bola dan hapus jejak serta waktu simulasi
+    pub fn reset(&mut self) {
+        self.dirty = true;
+        self.paused = false;
+        self.on_obstacle = false;
+        self.horizontal.stop();
+        self.vertical.stop();
+        self.sim_time.reset();
+        self.ticker_marks.clear();
+        self.ghosts.clear();
+        self.trail.clear();
+        self.next_tick = Duration::ZERO;
+        self.energy_history.clear();
+    }
+
+    /// kotak yang ditempati bola, dalam koordinat `pos`
+    pub fn ball_rect(&self) -> egui::Rect {
+        egui::Rect::from_min_size(
+            self.pos,
+            self.radius_size() * 2.0,
+        )
+    }
+
+    /// pantulkan bola dari rintangan yang bersentuhan,
+    /// ke arah dengan tumpang tindih terkecil
+    fn collide_obstacles(&mut self) {
+        let restitution = self.vertical.restitution;
+
+        for index in 0..self.obstacles.len() {
+            let obstacle = self.obstacles[index];
+            let ball = self.ball_rect();
+            let overlap = ball.intersect(obstacle);
+            if !overlap.is_positive() {
+                continue;
+            }
+
+            let velocity_x = self.horizontal.velocity_x();
+            let velocity_up = self.vertical.velocity_up();
+
+            if overlap.width() < overlap.height() {
+                if ball.center().x < obstacle.center().x {
+                    self.pos.x -= overlap.width();
+                    if velocity_x > 0.0 {
+                        self.horizontal.bounce(restitution);
+                    }
+                } else {
+                    self.pos.x += overlap.width();
+                    if velocity_x < 0.0 {
+                        self.horizontal.bounce(restitution);
+                    }
+                }
+            } else if ball.center().y > obstacle.center().y
+            {
+                self.pos.y += overlap.height();
+                if self.vertical.is_play()
+                    && velocity_up <= 0.0
+                {
+                    self.vertical.bounce();
+                    if self.vertical.velocity_up()
+                        < OBSTACLE_REST_SPEED
+                    {
+                        self.vertical.stop();
+                        self.on_obstacle = true;
+                    }
+                }
+            } else {
+                self.pos.y -= overlap.height();
+                if self.vertical.is_play()
+                    && velocity_up > 0.0
+                {
+                    self.vertical.bounce();
+                }
+            }
+        }
+
+        // jatuh lagi setelah keluar dari atas rintangan
+        if self.on_obstacle && !self.vertical.is_play() {
+            let ball = self.ball_rect();
+            let supported =
+                self.obstacles.iter().any(|it| {
+                    (ball.min.y - it.max.y).abs() <= 1.0
+                        && ball.max.x > it.min.x
+                        && ball.min.x < it.max.x
+                });
+            if !supported {
+                self.on_obstacle = false;
+                self.vertical.fall();
+            }
+        }
+    }
+
+    /// kotak `world` dalam koordinat `pos` di layar
+    pub fn world_rect_to_screen(
+        &self,
+        world: egui::Rect,
+        rect: egui::Rect,
+    ) -> egui::Rect {
+        egui::Rect::from_two_pos(
+            self.point_to_screen_with(world.min, 0.0, rect),
+            self.point_to_screen_with(world.max, 0.0, rect),
+        )
+    }
+
+    /// perkecil kecepatan sampai tidak melebihi
+    /// [`Self::max_speed`]
+    fn limit_speed(&mut self) {
+        let max_speed = match self.max_speed {
+            Some(it) => it.max(0.0),
+            None => return,
+        };
+        let speed = self.snapshot().speed();
+        if speed <= max_speed {
+            return;
+        }
+
+        let scale = max_speed / speed;
+        if self.horizontal.is_play() {
+            self.horizontal.set_velocity(
+                self.horizontal.velocity * scale,
+            );
+        }
+        self.vertical.velocity *= scale;
+    }
+
+    /// gesekan lantai mengurangi kecepatan horizontal saat
+    /// bola memantul
+    fn floor_bounce(&mut self) {
+        if self.horizontal.is_play() {
+            self.horizontal.set_velocity(
+                self.horizontal.velocity
+                    * self.floor_friction,
+            );
+        }
+    }
+
+    /// pengali gravitasi dari zona yang berisi bola
+    pub fn gravity_scale(&self) -> f64 {
+        self.zones
+            .iter()
+            .find(|it| it.y.contains(&self.pos.y))
+            .map_or(1.0, |it| it.gravity_multiplier)
+    }
+
+    /// bandingkan posisi, kecepatan dan percepatan beserta
+    /// arahnya dengan toleransi `epsilon`, tanpa melihat
+    /// texture dan jam
+    pub fn approx_eq(
+        &self,
+        other: &Self,
+        epsilon: f64,
+    ) -> bool {
+        let eq = |a: f64, b: f64| (a - b).abs() <= epsilon;
+        let a = self.snapshot();
+        let b = other.snapshot();
+
+        self.horizontal.is_play()
+            == other.horizontal.is_play()
+            && self.vertical.is_play()
+                == other.vertical.is_play()
+            && eq(a.pos.x as f64, b.pos.x as f64)
+            && eq(a.pos.y as f64, b.pos.y as f64)
+            && eq(a.vx, b.vx)
+            && eq(a.vy, b.vy)
+            && eq(a.ax, b.ax)
+            && eq(a.ay, b.ay)
+    }
+
+    /// ubah ukuran area bermain
+    pub fn set_bounds(&mut self, size: egui::Vec2) {
+        if size != self.size {
+            self.dirty = true;
+        }
+        if self.scale_with_size && size != self.size {
+            self.rescale_for_size(size);
+        } else {
+            self.size = size;
+        }
+    }
+
+    /// ubah ukuran area sekaligus kecepatan dan percepatan
+    /// sebanding dengan perubahan ukuran, supaya gerakan
+    /// terlihat sama di ukuran jendela berbeda
+    pub fn rescale_for_size(
+        &mut self,
+        new_size: egui::Vec2,
+    ) {
+        let ratio = |new: f32, old: f32| {
+            if old > 0.0 && new > 0.0 {
+                (new / old) as f64
+            } else {
+                1.0
+            }
+        };
+        let x = ratio(new_size.x, self.size.x);
+        let y = ratio(new_size.y, self.size.y);
+
+        self.horizontal
+            .set_velocity(self.horizontal.velocity * x);
+        self.horizontal.set_acceleration(
+            self.horizontal.acceleration * x,
+        );
+        self.vertical.velocity *= y;
+        self.vertical.accel *= y;
+
+        self.size = new_size;
+    }
+
+    /// letakkan bola di tengah area bermain
+    pub fn spawn_centered(&mut self) {
+        self.dirty = true;
+        let center = self.size / 2.0;
+        let y = self.centered_y(center.y);
+        self.pos =
+            egui::pos2(center.x - self.radius_at(y), y);
+        self.clamp();
+    }
+
+    /// ketinggian `y` yang membuat titik tengah bola berada
+    /// di `center_y`, yaitu `y + radius_at(y) = center_y`
+    fn centered_y(&self, center_y: f32) -> f32 {
+        let radius = self.original_radius;
+        let height = self.size.y;
+
+        // di atas lantai radius_at(y) = R * (1 - y / 2H)
+        let y = if self.perspective
+            && height > 0.0
+            && center_y > radius
+        {
+            (center_y - radius)
+                / (1.0 - radius / (2.0 * height))
+        } else {
+            center_y - radius
+        };
+
+        // bola yang tinggi bisa tertahan di min_radius
+        if self.radius_at(y) <= self.min_radius {
+            center_y - self.min_radius
+        } else {
+            y
+        }
+    }
+
     /// jepit nilai posisi sehingga tidak melewati layar
     pub fn clamp(&mut self) {
         self.pos = self
@@ -62,11 +1570,24 @@ impl GLBBState {
 
     /// ambil radius dari bola yang sudah di scale dengan tinggi bola
     pub fn radius(&self) -> f32 {
-        let y =
-            self.pos.y.max(0.0) / (self.size.y.max(0.0));
+        self.radius_at(self.pos.y)
+    }
+
+    /// radius bola jika berada di ketinggian `y`
+    pub fn radius_at(&self, y: f32) -> f32 {
+        if !self.perspective {
+            return self.original_radius;
+        }
+
+        // ukuran belum diketahui, anggap bola di lantai
+        let y = if self.size.y > 0.0 {
+            y.max(0.0) / self.size.y
+        } else {
+            0.0
+        };
         let min_by = 0.5 * y;
         let scale = 1.0 - min_by.min(0.9);
-        self.original_radius * scale
+        (self.original_radius * scale).max(self.min_radius)
     }
 
     /// posisi maximum dari x
@@ -79,14 +1600,1110 @@ impl GLBBState {
         self.size.y - (self.radius() * 2.0)
     }
 
-    /// posisi maximum
+    /// posisi maximum.
+    ///
+    /// jika area lebih kecil dari bola hasilnya (1,1) dan bola
+    /// akan tertahan di pojok, pakai [`Self::try_pos_max`] untuk
+    /// mendeteksinya
     pub fn pos_max(&self) -> egui::Vec2 {
         (self.size - (self.radius_size() * 2.0))
             .max(egui::vec2(1f32, 1f32))
     }
 
+    /// posisi maximum, `None` jika area terlalu kecil untuk
+    /// bola
+    pub fn try_pos_max(&self) -> Option<egui::Vec2> {
+        let max = self.size - (self.radius_size() * 2.0);
+        (max.x > 0.0 && max.y > 0.0).then_some(max)
+    }
+
+    /// cek apakah bola berada di dalam area, dengan toleransi
+    /// setengah pixel. berguna untuk `debug_assert!`
+    pub fn is_in_bounds(&self) -> bool {
+        const TOLERANCE: f32 = 0.5;
+
+        let max = match self.try_pos_max() {
+            Some(max) => max,
+            None => return false,
+        };
+        let in_range = |pos: f32, max: f32| {
+            (-TOLERANCE..=max + TOLERANCE).contains(&pos)
+        };
+
+        in_range(self.pos.x, max.x)
+            && in_range(self.pos.y, max.y)
+    }
+
     /// radius dalam bentuk [radius,radius]
     pub fn radius_size(&self) -> egui::Vec2 {
         [self.radius(), self.radius()].into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// state dengan area bermain `width` x `height`
+    fn state_with_size(
+        width: f32,
+        height: f32,
+    ) -> GLBBState {
+        let mut state = GLBBState::default();
+        state.set_bounds(egui::vec2(width, height));
+        state
+    }
+
+    #[test]
+    fn screen_rect_is_centered_on_the_ball() {
+        let mut state = state_with_size(400.0, 300.0);
+        state.pos = egui::pos2(120.0, 80.0);
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(10.0, 20.0),
+            state.size,
+        );
+
+        let screen = state.screen_rect(rect);
+
+        assert_eq!(
+            screen.center(),
+            state.pos_to_screen(rect)
+        );
+        assert_eq!(
+            screen.size(),
+            egui::Vec2::splat(2.0 * state.radius())
+        );
+    }
+
+    #[test]
+    fn buoyant_zone_turns_the_ball_back_up() {
+        let mut state = state_with_size(400.0, 600.0);
+        state.zones.push(GravityZone {
+            y: 0.0..=200.0,
+            gravity_multiplier: -2.0,
+        });
+        state.pos.y = 400.0;
+        state.fall();
+
+        let mut lowest = state.pos.y;
+        let mut rose_in_zone = false;
+        for _ in 0..300 {
+            state.step(Duration::from_millis(16));
+            lowest = lowest.min(state.pos.y);
+            rose_in_zone |= state.pos.y < 200.0
+                && state.vertical.velocity_up() > 0.0;
+        }
+
+        assert!(rose_in_zone);
+        // mengapung di sekitar permukaan, tidak sampai
+        // tenggelam ke lantai
+        assert!(lowest > 50.0, "{}", lowest);
+        assert!(state.is_play());
+    }
+
+    #[test]
+    fn distance_traveled_adds_both_legs_of_a_wall_bounce() {
+        let mut state = state_with_size(400.0, 300.0);
+        let wall = state.pos_max().x;
+        state.pos.x = wall - 100.0;
+        state.horizontal.set_velocity(200.0);
+        state.horizontal.set_acceleration(100.0);
+        state.play_right();
+
+        while state.horizontal.is_play() {
+            state.step(Duration::from_millis(16));
+        }
+
+        // dari awal ke dinding lalu dari dinding ke akhir
+        let legs = 100.0 + (wall - state.pos.x) as f64;
+        let traveled = state.horizontal_distance_traveled();
+        assert!(state.pos.x < wall - 50.0);
+        assert!(
+            (traveled - legs).abs() < 1e-3,
+            "{}",
+            traveled
+        );
+    }
+
+    #[test]
+    fn long_stall_does_not_tunnel_out_of_bounds() {
+        let mut state = state_with_size(400.0, 300.0);
+        state.pos = egui::pos2(200.0, 200.0);
+        state.horizontal.set_velocity(5000.0);
+        state.horizontal.set_acceleration(10.0);
+        state.play_right();
+        state.fall();
+
+        state.step(Duration::from_secs(5));
+
+        assert_eq!(
+            state.frame_dt(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(state.sim_time(), DEFAULT_MAX_DT);
+        assert!(state.is_in_bounds(), "{:?}", state.pos);
+    }
+
+    #[test]
+    fn floor_friction_slows_the_ball_at_each_bounce() {
+        let mut state = state_with_size(4000.0, 600.0);
+        state.floor_friction = 0.5;
+        state.pos.y = 400.0;
+        state.horizontal.set_velocity(300.0);
+        state.horizontal.set_acceleration(0.0);
+        state.play_right();
+        state.fall();
+
+        let mut speeds = vec![state.horizontal.velocity];
+        for _ in 0..200 {
+            if state
+                .step(Duration::from_millis(16))
+                .is_some()
+            {
+                speeds.push(state.horizontal.velocity);
+            }
+        }
+
+        assert!(speeds.len() > 2, "{:?}", speeds);
+        for it in speeds.windows(2) {
+            assert!((it[1] - it[0] * 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn radius_never_drops_below_min_radius() {
+        let mut state = state_with_size(400.0, 300.0);
+        state.min_radius = 20.0;
+        state.pos.y = state.pos_max().y;
+
+        assert!(state.radius() >= state.min_radius);
+        assert_eq!(
+            state.radius_at(state.size.y),
+            state.min_radius
+        );
+    }
+
+    #[test]
+    fn time_to_floor_matches_a_pure_drop() {
+        let mut state = state_with_size(400.0, 1000.0);
+        assert_eq!(state.time_to_floor(), None);
+
+        state.pos.y = 500.0;
+        state.fall();
+
+        let expected =
+            (2.0 * 500.0 / DEFAULT_GRAVITY).sqrt();
+        let time =
+            state.time_to_floor().unwrap().as_secs_f64();
+        assert!((time - expected).abs() < 1e-9, "{}", time);
+    }
+
+    /// simpanan dari versi sebelum ada `version`
+    #[cfg(feature = "serde")]
+    const V0_RON: &str = "(
+        pos: (x: 120.0, y: 45.5),
+        original_radius: 0.0,
+        size: (x: 640.0, y: 480.0),
+        horizontal: (
+            play: None,
+            velocity: 250.0,
+            acceleration: 40.0,
+        ),
+        vertical: (
+            play: false,
+            direction: -1.0,
+            accel: 800.0,
+            velocity: 0.0,
+        ),
+    )";
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn v0_save_migrates_without_losing_fields() {
+        let mut state: GLBBState =
+            ron::from_str(V0_RON).unwrap();
+        assert_eq!(state.version, 0);
+
+        state.migrate();
+
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.pos, egui::pos2(120.0, 45.5));
+        assert_eq!(state.size, egui::vec2(640.0, 480.0));
+        assert_eq!(state.horizontal.velocity, 250.0);
+        assert_eq!(state.horizontal.acceleration, 40.0);
+        assert_eq!(state.vertical.accel, 800.0);
+        assert!(!state.is_play());
+        // kecepatan awal diambil dari kecepatan yang tersimpan
+        assert_eq!(state.export_config().velocity, 250.0);
+        assert_eq!(state.original_radius, DEFAULT_RADIUS);
+    }
+
+    #[test]
+    fn replay_launch_restarts_from_the_launch() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.pos = egui::pos2(100.0, 50.0);
+        state.horizontal.set_velocity(300.0);
+        state.horizontal.set_acceleration(60.0);
+        state.play_right();
+        let launched = state.snapshot();
+
+        for _ in 0..30 {
+            state.step(Duration::from_millis(16));
+        }
+        assert_ne!(state.snapshot(), launched);
+
+        state.replay_launch();
+
+        assert_eq!(state.snapshot(), launched);
+        assert_eq!(state.sim_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn spawn_centered_puts_the_ball_center_at_the_midpoint()
+    {
+        let mut state = GLBBState::default();
+        for (size, min_radius) in [
+            (egui::vec2(400.0, 300.0), 0.0),
+            (egui::vec2(640.0, 2000.0), 0.0),
+            (egui::vec2(640.0, 2000.0), 25.0),
+        ] {
+            state.set_bounds(size);
+            state.min_radius = min_radius;
+            state.spawn_centered();
+
+            let center = state.pos + state.radius_size();
+            assert!(
+                (center - (size / 2.0).to_pos2()).length()
+                    < 1e-3,
+                "{:?} {:?}",
+                size,
+                center
+            );
+        }
+    }
+
+    #[test]
+    fn approx_eq_tells_perturbed_states_apart() {
+        let moving = |right: bool| {
+            let mut state = state_with_size(800.0, 600.0);
+            state.pos = egui::pos2(300.0, 200.0);
+            state.horizontal.set_velocity(250.0);
+            state.horizontal.set_acceleration(40.0);
+            if right {
+                state.play_right();
+            } else {
+                state.play_left();
+            }
+            state.step(Duration::from_millis(16));
+            state
+        };
+        let state = moving(true);
+
+        assert!(state.approx_eq(&state, 0.0));
+        assert!(state.approx_eq(&moving(true), 1e-9));
+
+        let mut nudged = moving(true);
+        nudged.pos.y += 0.5;
+        assert!(!state.approx_eq(&nudged, 1e-3));
+        assert!(state.approx_eq(&nudged, 1.0));
+
+        let mut left = moving(false);
+        left.pos = state.pos;
+        assert!(!state.approx_eq(&left, 1e-3));
+
+        let mut stopped = moving(true);
+        stopped.horizontal.stop();
+        assert!(!state.approx_eq(&stopped, 1e3));
+    }
+
+    #[test]
+    fn screen_round_trip_for_both_origins() {
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(10.0, 20.0),
+            egui::vec2(400.0, 300.0),
+        );
+        let mut state = state_with_size(400.0, 300.0);
+
+        for origin in [
+            CoordinateOrigin::BottomLeft,
+            CoordinateOrigin::TopLeft,
+        ] {
+            state.origin = origin;
+            for pos in
+                [(0.0, 0.0), (100.0, 50.0), (300.0, 200.0)]
+            {
+                state.pos = pos.into();
+                let screen = state.pos_to_screen(rect);
+                assert_eq!(
+                    state.pos_from_screen(rect, screen),
+                    state.pos,
+                    "{:?}",
+                    origin
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn screen_round_trip_for_several_radii() {
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(7.5, 13.25),
+            egui::vec2(640.0, 480.0),
+        );
+
+        for radius in [1.0, 12.5, 30.0, 75.0] {
+            let mut state = GLBBState::new(radius);
+            state.set_bounds(rect.size());
+            for pos in
+                [(0.0, 0.0), (33.3, 17.9), (250.0, 300.0)]
+            {
+                state.pos = pos.into();
+                let back = state.pos_from_screen(
+                    rect,
+                    state.pos_to_screen(rect),
+                );
+                assert!(
+                    (back - state.pos).length() <= 1e-4,
+                    "{} {:?} {:?}",
+                    radius,
+                    state.pos,
+                    back
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ticker_drops_a_mark_every_interval() {
+        let mut state = state_with_size(2000.0, 600.0);
+        state.ticker_interval =
+            Some(Duration::from_millis(100));
+        state.play_right();
+
+        for _ in 0..100 {
+            state.step(Duration::from_millis(10));
+        }
+
+        // satu tanda saat peluncuran, lalu satu tiap 0.1 s
+        let marks = state.ticker_marks().len();
+        assert!((10..=11).contains(&marks), "{}", marks);
+    }
+
+    #[test]
+    fn try_pos_max_is_none_when_the_ball_does_not_fit() {
+        let diameter = GLBBState::default().radius() * 2.0;
+
+        let state = state_with_size(diameter - 1.0, 400.0);
+        assert_eq!(state.try_pos_max(), None);
+        assert_eq!(state.pos_max().x, 1.0);
+
+        let state = state_with_size(400.0, diameter);
+        assert_eq!(state.try_pos_max(), None);
+
+        let state = state_with_size(400.0, 400.0);
+        assert!(state.try_pos_max().is_some());
+    }
+
+    #[test]
+    fn impulse_on_a_resting_ball_starts_a_fresh_motion() {
+        let mut state = state_with_size(2000.0, 600.0);
+        state.billiard_mode(true);
+        state.play_right();
+        for _ in 0..10 {
+            state.step(Duration::from_millis(50));
+        }
+        state.reset();
+        state.billiard_mode(false);
+        state.horizontal.set_acceleration(100.0);
+
+        state.apply_impulse(egui::vec2(-120.0, 300.0));
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.vx, -120.0);
+        assert_eq!(snapshot.vy, 300.0);
+        assert!(!state.horizontal.is_constant());
+        assert_eq!(
+            state.horizontal_distance_traveled(),
+            0.0
+        );
+        assert_eq!(state.horizontal.progress(), 0.0);
+
+        state.step(Duration::from_millis(100));
+        assert!(state.horizontal.progress() > 0.0);
+        assert!(state.snapshot().vy < 300.0);
+    }
+
+    #[test]
+    fn is_in_bounds_catches_a_ball_outside_the_area() {
+        let mut state = state_with_size(400.0, 300.0);
+        let max = state.pos_max();
+
+        for pos in [egui::pos2(0.0, 0.0), max.to_pos2()] {
+            state.pos = pos;
+            assert!(state.is_in_bounds(), "{:?}", pos);
+        }
+
+        for pos in [
+            egui::pos2(-5.0, 10.0),
+            egui::pos2(10.0, 305.0),
+            egui::pos2(max.x + 5.0, 0.0),
+        ] {
+            state.pos = pos;
+            assert!(!state.is_in_bounds(), "{:?}", pos);
+        }
+    }
+
+    #[test]
+    fn pause_and_resume_continue_the_same_trajectory() {
+        let launched = || {
+            let mut state = state_with_size(2000.0, 1000.0);
+            state.launch_projectile(400.0, 60.0);
+            state
+        };
+        let dt = Duration::from_millis(20);
+
+        let mut straight = launched();
+        for _ in 0..20 {
+            straight.step(dt);
+        }
+
+        let mut paused = launched();
+        for _ in 0..10 {
+            paused.step(dt);
+        }
+        paused.pause();
+        let frozen = paused.snapshot();
+        for _ in 0..10 {
+            assert_eq!(paused.step(dt), None);
+        }
+        assert_eq!(paused.snapshot(), frozen);
+        paused.resume();
+        for _ in 0..10 {
+            paused.step(dt);
+        }
+
+        assert!(straight.approx_eq(&paused, 1e-6));
+        assert_eq!(straight.sim_time(), paused.sim_time());
+    }
+
+    #[test]
+    fn elastic_frictionless_run_keeps_total_energy_flat() {
+        let mut state = state_with_size(400.0, 2000.0);
+        state.vertical.restitution = 1.0;
+        state.floor_friction = 0.0;
+        state.integrator(Integrator::Rk4);
+        state.pos.y = 500.0;
+        state.fall();
+
+        for _ in 0..300 {
+            state.step(Duration::from_millis(10));
+        }
+
+        let total: Vec<f64> = state
+            .total_energy_series()
+            .iter()
+            .map(|it| it[1])
+            .collect();
+        assert!(total.len() > 100, "{}", total.len());
+
+        let n = total.len() as f64;
+        let mean = total.iter().sum::<f64>() / n;
+        let variance = total
+            .iter()
+            .map(|it| (it - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        // sisa langkah saat menyentuh lantai tidak ikut
+        // dipantulkan, jadi energi sedikit bergeser tiap
+        // pantulan
+        let relative = variance.sqrt() / mean;
+        assert!(relative < 5e-3, "{}", relative);
+    }
+
+    #[test]
+    fn contains_screen_point_is_limited_to_the_ball() {
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(10.0, 20.0),
+            egui::vec2(400.0, 300.0),
+        );
+        let mut state = state_with_size(400.0, 300.0);
+        state.pos = egui::pos2(150.0, 100.0);
+        let center = state.pos_to_screen(rect);
+        let radius = state.radius();
+
+        assert!(state.contains_screen_point(rect, center));
+        for direction in [
+            egui::vec2(1.0, 0.0),
+            egui::vec2(0.0, -1.0),
+            egui::vec2(-0.6, 0.8),
+        ] {
+            let inside =
+                center + direction * (radius - 0.1);
+            let outside =
+                center + direction * (radius + 0.1);
+            assert!(
+                state.contains_screen_point(rect, inside)
+            );
+            assert!(
+                !state.contains_screen_point(rect, outside)
+            );
+        }
+    }
+
+    #[test]
+    fn doubling_the_width_doubles_the_velocity_when_scaling(
+    ) {
+        let resized = |scale_with_size: bool| {
+            let mut state = state_with_size(400.0, 300.0);
+            state.scale_with_size = scale_with_size;
+            state.horizontal.set_velocity(120.0);
+            state.horizontal.set_acceleration(30.0);
+            state.set_bounds(egui::vec2(800.0, 300.0));
+            state
+        };
+
+        let scaled = resized(true);
+        assert_eq!(scaled.horizontal.velocity, 240.0);
+        assert_eq!(scaled.horizontal.acceleration, 60.0);
+        assert_eq!(scaled.size, egui::vec2(800.0, 300.0));
+
+        let fixed = resized(false);
+        assert_eq!(fixed.horizontal.velocity, 120.0);
+        assert_eq!(fixed.size, egui::vec2(800.0, 300.0));
+    }
+
+    #[test]
+    fn rising_ball_moves_up_the_screen() {
+        let mut state = state_with_size(400.0, 300.0);
+        state.apply_impulse(egui::vec2(50.0, 300.0));
+        let dt = Duration::from_millis(100);
+
+        assert_eq!(
+            state.screen_velocity(dt),
+            egui::vec2(5.0, -30.0)
+        );
+
+        // y world sudah ke bawah, tidak perlu dibalik
+        state.origin = CoordinateOrigin::TopLeft;
+        assert_eq!(
+            state.screen_velocity(dt),
+            egui::vec2(5.0, 30.0)
+        );
+    }
+
+    #[test]
+    fn default_ball_is_visible_and_bad_radii_are_rejected()
+    {
+        let mut state = GLBBState::default();
+        assert_eq!(state.original_radius, DEFAULT_RADIUS);
+        assert!(state.radius() > 0.0);
+
+        for radius in [-5.0, 0.0, f32::NAN, f32::INFINITY] {
+            assert!(!state.set_original_radius(radius));
+            assert_eq!(
+                state.original_radius,
+                DEFAULT_RADIUS
+            );
+        }
+        assert_eq!(
+            GLBBState::new(-1.0).original_radius,
+            DEFAULT_RADIUS
+        );
+
+        assert!(state.set_original_radius(12.5));
+        assert_eq!(state.original_radius, 12.5);
+    }
+
+    #[test]
+    fn billiard_mode_keeps_speed_over_many_bounces() {
+        let mut state = state_with_size(300.0, 300.0);
+        state.horizontal.set_acceleration(100.0);
+        state.billiard_mode(true);
+        state.pos = egui::pos2(100.0, 100.0);
+        state.launch_projectile(400.0, 30.0);
+        let initial = state.snapshot().speed();
+
+        for _ in 0..500 {
+            state.step(Duration::from_millis(16));
+            let speed = state.snapshot().speed();
+            assert!(
+                (speed - initial).abs() < 1e-6,
+                "{}",
+                speed
+            );
+        }
+        assert!(
+            state.horizontal_distance_traveled() > 1000.0
+        );
+
+        let velocity_x = state.snapshot().vx;
+        state.billiard_mode(false);
+        assert_eq!(state.snapshot().vx, velocity_x);
+        assert!(!state.horizontal.is_constant());
+
+        state.step(Duration::from_millis(100));
+        assert!(
+            state.snapshot().vx.abs() < velocity_x.abs()
+        );
+    }
+
+    #[test]
+    fn step_with_the_same_dts_gives_the_same_motion() {
+        let run = || {
+            let mut state = state_with_size(800.0, 600.0);
+            state.horizontal.set_acceleration(50.0);
+            state.pos.y = 300.0;
+            state.launch_projectile(350.0, 40.0);
+            for millis in [16, 17, 33, 8, 16, 50, 16, 16] {
+                state.step(Duration::from_millis(millis));
+            }
+            state
+        };
+
+        let a = run();
+        let b = run();
+        assert_eq!(a.snapshot(), b.snapshot());
+        assert_eq!(
+            a.sim_time(),
+            Duration::from_millis(172)
+        );
+        assert_eq!(a.frame_dt(), Duration::from_millis(16));
+        assert_ne!(a.pos, egui::pos2(0.0, 300.0));
+    }
+
+    /// state dengan pengaturan selain bawaan yang sedang
+    /// bergerak
+    fn configured_state() -> GLBBState {
+        let mut state = state_with_size(800.0, 600.0);
+        state.horizontal.set_velocity(320.0);
+        state.horizontal.set_acceleration(45.0);
+        state.vertical.gravity = 500.0;
+        state.vertical.restitution = 0.6;
+        state.floor_friction = 0.25;
+        state.set_original_radius(18.0);
+        state.min_radius = 4.0;
+        state.pos = egui::pos2(200.0, 150.0);
+        state.play_right();
+        state
+    }
+
+    #[test]
+    fn applying_an_exported_config_resets_the_ball() {
+        let config = configured_state().export_config();
+
+        let mut state = state_with_size(800.0, 600.0);
+        state.pos = egui::pos2(300.0, 250.0);
+        state.apply_impulse(egui::vec2(100.0, 100.0));
+        state.apply_config(config.clone());
+
+        assert_eq!(state.export_config(), config);
+        assert_eq!(state.pos, egui::Pos2::ZERO);
+        assert!(!state.is_play());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_round_trips_through_ron() {
+        let config = configured_state().export_config();
+        let text = ron::to_string(&config).unwrap();
+        assert_eq!(
+            ron::from_str::<Config>(&text).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn ball_bounces_off_the_top_of_an_obstacle() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.perspective(false);
+        state.obstacles.push(egui::Rect::from_min_max(
+            egui::pos2(100.0, 0.0),
+            egui::pos2(300.0, 100.0),
+        ));
+        state.pos = egui::pos2(150.0, 300.0);
+        state.fall();
+
+        let mut bounced = false;
+        for _ in 0..100 {
+            assert_eq!(
+                state.step(Duration::from_millis(10)),
+                None
+            );
+            assert!(state.pos.y >= 99.0, "{:?}", state.pos);
+            if state.snapshot().vy > 0.0 {
+                bounced = true;
+                break;
+            }
+        }
+        assert!(bounced);
+    }
+
+    #[test]
+    fn ball_bounces_off_the_side_of_an_obstacle() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.perspective(false);
+        state.obstacles.push(egui::Rect::from_min_max(
+            egui::pos2(200.0, 0.0),
+            egui::pos2(260.0, 150.0),
+        ));
+        state.pos = egui::pos2(0.0, 0.0);
+        state.horizontal.set_velocity(300.0);
+        state.horizontal.set_acceleration(10.0);
+        state.play_right();
+        let diameter = state.radius() * 2.0;
+
+        let mut bounced = false;
+        for _ in 0..100 {
+            state.step(Duration::from_millis(10));
+            assert!(
+                state.pos.x + diameter <= 201.0,
+                "{:?}",
+                state.pos
+            );
+            if state.snapshot().vx < 0.0 {
+                bounced = true;
+                break;
+            }
+        }
+        assert!(bounced);
+    }
+
+    #[test]
+    fn ghosts_keep_every_stride_up_to_count() {
+        let mut state = state_with_size(4000.0, 600.0);
+        state.horizontal.set_velocity(300.0);
+        state.billiard_mode(true);
+        state.play_right();
+
+        let frame = |state: &mut GLBBState| {
+            state.step(Duration::from_millis(16));
+            state.record_ghost(5, 3);
+        };
+        for _ in 0..7 {
+            frame(&mut state);
+        }
+        assert_eq!(state.ghosts().len(), 2);
+
+        for _ in 0..30 {
+            frame(&mut state);
+        }
+        assert_eq!(state.ghosts().len(), 5);
+        let ghosts: Vec<_> =
+            state.ghosts().iter().collect();
+        assert!(ghosts
+            .windows(2)
+            .all(|it| it[0].x < it[1].x));
+    }
+
+    #[test]
+    fn new_state_is_safe_before_the_size_is_known() {
+        let mut state = GLBBState::new(30.0);
+        assert_eq!(state.size, egui::Vec2::ZERO);
+
+        let radius = state.radius();
+        assert!(
+            radius.is_finite() && radius > 0.0,
+            "{}",
+            radius
+        );
+        assert_eq!(state.try_pos_max(), None);
+
+        state.fall();
+        state.step(Duration::from_millis(16));
+        assert!(
+            state.pos.x.is_finite()
+                && state.pos.y.is_finite()
+        );
+        assert!(state.radius().is_finite());
+    }
+
+    #[test]
+    fn dominant_axis_follows_the_faster_component() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.pos.y = 100.0;
+        assert_eq!(state.dominant_axis(), None);
+
+        state.apply_impulse(egui::vec2(300.0, 20.0));
+        assert_eq!(
+            state.dominant_axis(),
+            Some(Axis::Horizontal)
+        );
+
+        state.reset();
+        state.apply_impulse(egui::vec2(-20.0, 300.0));
+        assert_eq!(
+            state.dominant_axis(),
+            Some(Axis::Vertical)
+        );
+
+        state.reset();
+        assert_eq!(state.dominant_axis(), None);
+    }
+
+    #[test]
+    fn flight_time_matches_2_v_sin_over_g() {
+        let mut state = state_with_size(800.0, 600.0);
+        assert_eq!(state.flight_time(), Duration::ZERO);
+
+        for (speed, angle) in [(300.0, 30.0), (500.0, 75.0)]
+        {
+            state.launch_projectile(speed, angle);
+            let expected =
+                2.0 * speed * f64::to_radians(angle).sin()
+                    / DEFAULT_GRAVITY;
+            let time = state.flight_time().as_secs_f64();
+            assert!(
+                (time - expected).abs() < 1e-9,
+                "{}",
+                time
+            );
+        }
+
+        state.fall();
+        assert_eq!(state.flight_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn radius_is_constant_without_perspective() {
+        let mut state = state_with_size(400.0, 600.0);
+        state.pos.y = 400.0;
+        assert!(state.radius() < state.original_radius);
+
+        state.perspective(false);
+        for y in [0.0, 150.0, 400.0, 600.0] {
+            state.pos.y = y;
+            assert_eq!(
+                state.radius(),
+                state.original_radius
+            );
+            assert_eq!(
+                state.radius_at(y),
+                state.original_radius
+            );
+        }
+    }
+
+    #[test]
+    fn mutations_set_the_dirty_flag_and_take_clears_it() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.take_dirty();
+        assert!(!state.take_dirty());
+
+        let mutations: [fn(&mut GLBBState); 6] = [
+            |it| it.play_right(),
+            |it| it.reset(),
+            |it| it.apply_impulse(egui::vec2(0.0, 100.0)),
+            |it| it.billiard_mode(true),
+            |it| it.spawn_centered(),
+            |it| {
+                it.set_original_radius(12.0);
+            },
+        ];
+        for mutate in mutations {
+            mutate(&mut state);
+            assert!(state.take_dirty());
+            assert!(!state.take_dirty());
+        }
+
+        state.step(Duration::from_millis(16));
+        assert!(state.take_dirty());
+
+        // bola diam tidak mengubah apa pun
+        state.reset();
+        state.take_dirty();
+        state.step(Duration::from_millis(16));
+        assert!(!state.take_dirty());
+    }
+
+    /// bola di dekat dinding kanan yang bergerak ke kanan
+    /// dengan kecepatan tetap
+    fn heading_right(behavior: WallBehavior) -> GLBBState {
+        let mut state = state_with_size(400.0, 300.0);
+        state.perspective(false);
+        state.horizontal.set_velocity(200.0);
+        state.billiard_mode(true);
+        state.set_wall(Wall::Right, behavior);
+        state.pos.x = state.pos_max().x - 10.0;
+        state.play_right();
+        state
+    }
+
+    #[test]
+    fn wrap_edges_reenters_on_the_left_at_the_same_speed() {
+        let mut state = heading_right(WallBehavior::Bounce);
+        state.wrap_edges(true);
+        assert!(state.is_wrap_edges());
+
+        state.step(Duration::from_millis(100));
+
+        // 20 ke kanan, 10 melewati dinding kanan
+        assert!(
+            (state.pos.x - 10.0).abs() < 1e-3,
+            "{}",
+            state.pos.x
+        );
+        assert_eq!(state.snapshot().vx, 200.0);
+
+        state.wrap_edges(false);
+        assert!(!state.is_wrap_edges());
+        assert_eq!(
+            state.wall(Wall::Right),
+            WallBehavior::Bounce
+        );
+    }
+
+    #[test]
+    fn each_wall_behavior_at_the_right_wall() {
+        let dt = Duration::from_millis(100);
+
+        let mut bounce =
+            heading_right(WallBehavior::Bounce);
+        let max = bounce.pos_max().x;
+        bounce.step(dt);
+        assert!((bounce.pos.x - (max - 10.0)).abs() < 1e-3);
+        assert_eq!(bounce.snapshot().vx, -200.0);
+
+        let mut clamp = heading_right(WallBehavior::Clamp);
+        clamp.pos.y = 100.0;
+        clamp.vertical.fall();
+        clamp.step(dt);
+        assert_eq!(clamp.pos.x, max);
+        assert!(!clamp.horizontal.is_play());
+        assert!(clamp.vertical.is_play());
+
+        let mut wrap = heading_right(WallBehavior::Wrap);
+        wrap.step(dt);
+        assert!((wrap.pos.x - 10.0).abs() < 1e-3);
+        assert_eq!(wrap.snapshot().vx, 200.0);
+
+        let mut stop = heading_right(WallBehavior::Stop);
+        stop.pos.y = 100.0;
+        stop.vertical.fall();
+        stop.step(dt);
+        assert_eq!(stop.pos.x, max);
+        assert!(!stop.is_play());
+    }
+
+    #[test]
+    fn history_buffers_stay_capped_with_the_latest_samples()
+    {
+        let mut state = state_with_size(4000.0, 600.0);
+        state.history_capacity = 4;
+        state.ticker_interval =
+            Some(Duration::from_millis(16));
+        state.horizontal.set_velocity(300.0);
+        state.billiard_mode(true);
+        state.play_right();
+
+        let mut positions = Vec::new();
+        for _ in 0..20 {
+            state.step(Duration::from_millis(16));
+            state.record_trail(100);
+            positions.push(state.pos);
+        }
+
+        assert_eq!(state.trail().len(), 4);
+        assert!(state.trail().iter().eq(&positions[16..]));
+        assert_eq!(state.ticker_marks().len(), 4);
+        assert_eq!(state.energy_history().count(), 4);
+    }
+
+    #[test]
+    fn speed_never_exceeds_the_cap() {
+        let mut state = state_with_size(4000.0, 2000.0);
+        state.max_speed = Some(250.0);
+        state.pos.y = 1500.0;
+        state.horizontal.set_acceleration(10.0);
+        state.launch_projectile(600.0, 20.0);
+
+        for _ in 0..200 {
+            state.step(Duration::from_millis(16));
+            let speed = state.snapshot().speed();
+            assert!(speed <= 250.0 + 1e-9, "{}", speed);
+        }
+    }
+
+    #[test]
+    fn dropped_ball_eventually_comes_to_rest() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.pos.y = 400.0;
+        state.fall();
+        assert!(state.is_play());
+
+        for _ in 0..10_000 {
+            if !state.is_play() {
+                break;
+            }
+            // satu frame 16 ms tanpa menunggu
+            state.frame = Now::with_elapsed(
+                Duration::from_millis(16),
+            );
+            state.mv();
+        }
+
+        assert!(!state.is_play());
+        assert!(!state.vertical.is_play());
+    }
+
+    #[test]
+    fn launch_origin_ignores_the_pre_launch_position() {
+        let mut state = state_with_size(800.0, 600.0);
+        let origin = egui::pos2(30.0, 40.0);
+        state.launch_origin = Some(origin);
+
+        let mut paths = vec![];
+        for before in [
+            egui::pos2(500.0, 300.0),
+            egui::pos2(5.0, 550.0),
+        ] {
+            state.pos = before;
+            state.launch_projectile(300.0, 45.0);
+            assert_eq!(state.pos, origin);
+
+            let mut path = vec![];
+            for _ in 0..10 {
+                state.step(Duration::from_millis(16));
+                path.push(state.pos);
+            }
+            paths.push(path);
+        }
+        assert_eq!(paths[0], paths[1]);
+
+        state.replay_launch();
+        assert_eq!(state.pos, origin);
+    }
+
+    #[test]
+    fn trajectory_starts_at_the_ball_on_screen() {
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(10.0, 20.0),
+            egui::vec2(800.0, 600.0),
+        );
+        let mut state = configured_state();
+        state.launch_projectile(400.0, 60.0);
+
+        let points = state.trajectory_screen_points(
+            rect,
+            20,
+            Duration::from_millis(16),
+        );
+
+        assert_eq!(points.len(), 20);
+        assert_eq!(points[0], state.pos_to_screen(rect));
+        assert_ne!(points[1], points[0]);
+    }
+
+    #[test]
+    fn fall_without_gravity_does_not_play() {
+        let mut state = state_with_size(800.0, 600.0);
+        state.pos.y = 300.0;
+        state.billiard_mode(true);
+
+        state.fall();
+
+        assert!(!state.is_play());
+        assert!(state.is_at_rest());
+        state.step(Duration::from_millis(16));
+        assert_eq!(state.pos.y, 300.0);
+    }
+}
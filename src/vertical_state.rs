@@ -1,72 +1,745 @@
-use crate::{calculate_distance, calculate_velocity, Now};
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Default, Serialize, Deserialize, Debug)]
+use crate::{
+    calculate_distance, calculate_velocity, WallBehavior,
+};
+
+/// percepatan gravitasi saat bola dijatuhkan
+pub const DEFAULT_GRAVITY: f64 = 800.0;
+
+/// jarak terjauh bola dalam satu sub langkah
+const MAX_SUBSTEP_DISTANCE: f64 = 5.0;
+/// batas jumlah sub langkah dalam satu [`VerticalState::step`]
+const MAX_SUBSTEPS: u32 = 1000;
+
+/// jarak waktu terpendek antar pantulan di lantai dalam
+/// detik. pantulan yang lebih rapat dianggap sudah diam,
+/// supaya pantulan yang makin kecil tidak berulang tanpa
+/// akhir
+const MIN_BOUNCE_INTERVAL: f64 = 0.02;
+
+/// pengali kecepatan saat memantul jika tidak diatur
+pub const DEFAULT_RESTITUTION: f64 = 0.8;
+
+fn default_gravity() -> f64 {
+    DEFAULT_GRAVITY
+}
+
+fn default_restitution() -> f64 {
+    DEFAULT_RESTITUTION
+}
+
+/// cara menghitung posisi dan kecepatan bola di setiap sub
+/// langkah
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Integrator {
+    /// kecepatan diperbarui dulu, lalu jarak dari kecepatan
+    /// yang baru
+    #[default]
+    SemiImplicitEuler,
+    /// verlet posisi, jarak dari jarak sub langkah
+    /// sebelumnya tanpa memakai kecepatan
+    Verlet,
+    /// runge-kutta orde 4
+    Rk4,
+}
+
+impl Integrator {
+    /// kecepatan dan jarak setelah `time` dengan kecepatan
+    /// awal `velocity` dan percepatan tetap `accel`.
+    ///
+    /// `previous` berisi jarak dan lama sub langkah
+    /// sebelumnya, hanya dipakai [`Integrator::Verlet`]
+    fn integrate(
+        self,
+        velocity: f64,
+        accel: f64,
+        time: f64,
+        previous: Option<(f64, f64)>,
+    ) -> (f64, f64) {
+        match self {
+            Integrator::SemiImplicitEuler => {
+                let velocity = calculate_velocity(
+                    velocity, accel, time,
+                );
+                (velocity, velocity * time)
+            }
+            Integrator::Verlet => match previous {
+                // x1 = x0 + (x0 - x-1) * t / t0
+                //      + a * t * (t + t0) / 2
+                Some((last_distance, last_time))
+                    if last_time > 0.0 && time > 0.0 =>
+                {
+                    let distance = last_distance * time
+                        / last_time
+                        + accel * time * (time + last_time)
+                            / 2.0;
+                    (
+                        distance / time
+                            + accel * time / 2.0,
+                        distance,
+                    )
+                }
+                // langkah pertama belum punya posisi lama
+                _ => (
+                    calculate_velocity(
+                        velocity, accel, time,
+                    ),
+                    calculate_distance(
+                        velocity, accel, time,
+                    ),
+                ),
+            },
+            Integrator::Rk4 => {
+                // x' = v, v' = a
+                let k1 = (velocity, accel);
+                let k2 =
+                    (velocity + k1.1 * time / 2.0, accel);
+                let k3 =
+                    (velocity + k2.1 * time / 2.0, accel);
+                let k4 = (velocity + k3.1 * time, accel);
+                (
+                    velocity
+                        + time / 6.0
+                            * (k1.1
+                                + 2.0 * k2.1
+                                + 2.0 * k3.1
+                                + k4.1),
+                    time / 6.0
+                        * (k1.0
+                            + 2.0 * k2.0
+                            + 2.0 * k3.0
+                            + k4.0),
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct VerticalState {
     play: bool,
-    #[serde(skip)]
-    start: Now,
     direction: f64,
 
     pub accel: f64,
     pub velocity: f64,
+
+    /// percepatan yang dipakai saat bola dijatuhkan atau
+    /// dilempar
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_gravity")
+    )]
+    pub gravity: f64,
+    /// pengali kecepatan setiap bola memantul di lantai atau
+    /// langit-langit, 1.0 berarti pantulan sempurna
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_restitution")
+    )]
+    pub restitution: f64,
+
+    /// perilaku bola di lantai
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub floor: WallBehavior,
+    /// perilaku bola di langit-langit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ceiling: WallBehavior,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub integrator: Integrator,
+
+    /// waktu simulasi sejak pantulan terakhir di lantai
+    #[cfg_attr(feature = "serde", serde(skip))]
+    since_bounce: f64,
+    /// jarak, lama dan kecepatan akhir sub langkah verlet
+    /// terakhir
+    #[cfg_attr(feature = "serde", serde(skip))]
+    verlet: Option<(f64, f64, f64)>,
+}
+
+impl Default for VerticalState {
+    fn default() -> Self {
+        Self {
+            play: Default::default(),
+            direction: Default::default(),
+            accel: Default::default(),
+            velocity: Default::default(),
+            gravity: default_gravity(),
+            restitution: default_restitution(),
+            floor: Default::default(),
+            ceiling: Default::default(),
+            integrator: Default::default(),
+            since_bounce: Default::default(),
+            verlet: Default::default(),
+        }
+    }
 }
 
 impl VerticalState {
+    /// state yang sedang bergerak, lihat [`Self::set_state`]
+    pub fn new(velocity_up: f64, accel: f64) -> Self {
+        let mut state = Self::default();
+        state.set_state(velocity_up, accel);
+        state
+    }
+
+    /// mulai gerakan dengan kecepatan `velocity_up`, positif
+    /// berarti ke atas, dan percepatan `accel` ke bawah
+    /// tanpa lewat [`Self::fall`]. riwayat pantulan dan
+    /// integrasi sebelumnya dibuang
+    pub fn set_state(
+        &mut self,
+        velocity_up: f64,
+        accel: f64,
+    ) {
+        self.accel = accel;
+        self.direction =
+            if velocity_up > 0.0 { 1.0 } else { -1.0 };
+        self.velocity = -self.direction * velocity_up;
+        self.play = true;
+        self.since_bounce = 0.0;
+        self.verlet = None;
+    }
+
     pub fn fall(&mut self) {
-        self.accel = 800.0;
+        self.accel = self.gravity;
         self.velocity = 0.0;
         self.play = true;
         self.direction = -1.0;
-        self.start.reset();
+        self.since_bounce = 0.0;
+    }
+
+    /// lempar bola dengan kecepatan `velocity_up`, positif
+    /// berarti ke atas
+    pub fn throw(&mut self, velocity_up: f64) {
+        self.set_state(velocity_up, self.gravity);
     }
 
     pub fn is_drop(&self) -> bool {
         self.direction.is_sign_negative()
     }
 
+    /// bola bergerak dan sedang naik. berbeda dengan
+    /// [`Self::is_drop`], dilihat dari tanda kecepatan
+    /// sekarang sehingga ikut berubah di titik tertinggi
+    pub fn is_rising(&self) -> bool {
+        self.play && self.velocity_up() > 0.0
+    }
+
+    /// bola bergerak dan sedang turun
+    pub fn is_falling(&self) -> bool {
+        self.play && self.velocity_up() < 0.0
+    }
+
     pub fn stop(&mut self) {
         self.play = false;
     }
 
-    pub fn mv(&mut self, pos: &mut f32, _: f32) {
+    /// kecepatan bola, positif berarti naik
+    pub fn velocity_up(&self) -> f64 {
+        -self.direction * self.velocity
+    }
+
+    /// percepatan bola, positif berarti ke atas
+    pub fn accel_up(&self, gravity_scale: f64) -> f64 {
         if self.play {
-            let time = self.start.elapsed().as_secs_f64();
-            self.velocity = calculate_velocity(
-                self.velocity,
-                self.accel * self.direction as f64,
-                time,
-            );
-            let mut distance = calculate_distance(
+            -self.accel * gravity_scale
+        } else {
+            0.0
+        }
+    }
+
+    /// perkiraan waktu sampai bola di `current_y` menyentuh
+    /// lantai dengan gravitasi `gravity`.
+    ///
+    /// `None` jika bola diam atau tidak akan sampai ke lantai
+    pub fn time_to_floor(
+        &self,
+        current_y: f64,
+        gravity: f64,
+    ) -> Option<Duration> {
+        if !self.play {
+            return None;
+        }
+
+        let velocity = self.velocity_up();
+
+        // y + v*t - g*t²/2 = 0
+        let time = if gravity == 0.0 {
+            if velocity >= 0.0 {
+                return None;
+            }
+            current_y / -velocity
+        } else {
+            let discriminant = velocity * velocity
+                + 2.0 * gravity * current_y;
+            if discriminant < 0.0 {
+                return None;
+            }
+            (velocity + discriminant.sqrt()) / gravity
+        };
+
+        // negatif atau terlalu lama untuk Duration
+        Duration::try_from_secs_f64(time).ok()
+    }
+
+    /// waktu sampai bola di `current_y` mencapai titik
+    /// tertinggi beserta ketinggiannya, `t = v/g` dan
+    /// `h = y + v²/2g`.
+    ///
+    /// `(0, current_y)` jika bola sedang turun atau diam.
+    /// waktu yang terlalu lama dibatasi [`Duration::MAX`]
+    pub fn apex(
+        &self,
+        current_y: f64,
+        gravity: f64,
+    ) -> (Duration, f64) {
+        let velocity = self.velocity_up();
+        if !self.play || velocity <= 0.0 || gravity <= 0.0 {
+            return (Duration::ZERO, current_y);
+        }
+
+        let time = velocity / gravity;
+        let height = current_y
+            + velocity * velocity / (2.0 * gravity);
+        (
+            Duration::try_from_secs_f64(time)
+                .unwrap_or(Duration::MAX),
+            height,
+        )
+    }
+
+    /// sisa energi mekanik bola di `current_y` dibanding saat
+    /// dijatuhkan diam dari `initial_height`, berkurang setiap
+    /// memantul
+    pub fn energy_fraction(
+        &self,
+        current_y: f64,
+        initial_height: f64,
+    ) -> f64 {
+        let initial = self.accel * initial_height;
+        if initial <= 0.0 {
+            return 0.0;
+        }
+
+        let velocity =
+            if self.play { self.velocity } else { 0.0 };
+        let current = 0.5 * velocity * velocity
+            + self.accel * current_y.max(0.0);
+        current / initial
+    }
+
+    /// gerakkan bola sejauh `dt`.
+    ///
+    /// mengembalikan kecepatan tumbukan jika bola memantul
+    /// di lantai pada langkah ini
+    pub fn step(
+        &mut self,
+        pos: &mut f32,
+        max: f32,
+        dt: Duration,
+        gravity_scale: f64,
+    ) -> Option<f64> {
+        if !self.play {
+            return None;
+        }
+
+        let time = dt.as_secs_f64();
+        let accel = self.accel * gravity_scale;
+
+        // bagi dt supaya setiap sub langkah bergerak tidak
+        // lebih dari MAX_SUBSTEP_DISTANCE, sehingga lantai
+        // tidak terlewat walaupun bola sangat cepat
+        let estimate = self.velocity.abs() * time
+            + 0.5 * accel.abs() * time * time;
+        let substeps = (estimate / MAX_SUBSTEP_DISTANCE)
+            .ceil()
+            .clamp(1.0, MAX_SUBSTEPS as f64)
+            as u32;
+        let sub_time = time / substeps as f64;
+
+        let mut bounce = None;
+        let mut distance = 0.0;
+        for _ in 0..substeps {
+            let (moved, hit) =
+                self.substep(pos, max, accel, sub_time);
+            distance += moved.abs();
+            if bounce.is_none() {
+                bounce = hit;
+            }
+            if !self.play {
+                break;
+            }
+        }
+
+        // hampir tidak bergerak di lantai, letakkan di lantai
+        if distance.abs() <= 0.5 && pos.abs() <= 0.5 {
+            self.hold(pos, 0.0, WallBehavior::Stop);
+        }
+
+        bounce
+    }
+
+    /// satu sub langkah integrasi, mengembalikan jarak
+    /// tempuh dan kecepatan tumbukan jika memantul di lantai
+    fn substep(
+        &mut self,
+        pos: &mut f32,
+        max: f32,
+        accel: f64,
+        time: f64,
+    ) -> (f64, Option<f64>) {
+        // riwayat verlet tidak berlaku lagi jika kecepatan
+        // diubah dari luar, misal saat memantul
+        let previous = self
+            .verlet
+            .filter(|it| it.2 == self.velocity)
+            .map(|it| (it.0, it.1));
+        let (velocity, distance) =
+            self.integrator.integrate(
                 self.velocity,
-                self.accel * self.direction,
+                accel * self.direction,
                 time,
+                previous,
             );
-            let new_distance = distance;
-
-            while distance != 0.0 {
-                let move_by = distance.min(5.0);
-                distance -= move_by;
-
-                *pos -= (move_by as f32)
-                    * (self.direction as f32);
-                if *pos <= 0.0 {
-                    self.direction *= -1.0;
-                    self.velocity *= 0.8;
-                    break;
+        self.velocity = velocity;
+        self.verlet = (self.integrator
+            == Integrator::Verlet)
+            .then_some((distance, time, velocity));
+        self.since_bounce += time;
+
+        *pos -= (distance as f32) * (self.direction as f32);
+        if *pos <= 0.0 {
+            match self.floor {
+                WallBehavior::Wrap if max > 0.0 => {
+                    *pos += max;
+                }
+                WallBehavior::Bounce
+                | WallBehavior::Wrap
+                    if self.since_bounce
+                        < MIN_BOUNCE_INTERVAL =>
+                {
+                    self.hold(pos, 0.0, WallBehavior::Stop);
+                }
+                WallBehavior::Bounce
+                | WallBehavior::Wrap => {
+                    *pos = 0.0;
+                    self.since_bounce = 0.0;
+                    let bounce = self.velocity.abs();
+                    self.bounce();
+                    return (distance, Some(bounce));
                 }
+                behavior => self.hold(pos, 0.0, behavior),
             }
+            return (distance, None);
+        }
 
-            self.start.reset();
-
-            if new_distance.abs() <= 0.5 && pos.abs() <= 0.5
-            {
-                self.play = false;
+        if *pos >= max && self.velocity_up() > 0.0 {
+            match self.ceiling {
+                WallBehavior::Wrap if max > 0.0 => {
+                    *pos -= max;
+                }
+                WallBehavior::Bounce
+                | WallBehavior::Wrap => {
+                    *pos = max;
+                    self.bounce();
+                }
+                behavior => self.hold(pos, max, behavior),
             }
         }
+
+        (distance, None)
+    }
+
+    /// tahan bola di sisi `at` untuk [`WallBehavior::Clamp`]
+    /// dan [`WallBehavior::Stop`]
+    fn hold(
+        &mut self,
+        pos: &mut f32,
+        at: f32,
+        behavior: WallBehavior,
+    ) {
+        *pos = at;
+        self.velocity = 0.0;
+        // di lantai tidak ada yang menarik bola kembali
+        if behavior == WallBehavior::Stop || at <= 0.0 {
+            self.play = false;
+        }
+    }
+
+    /// balik arah gerak dan kurangi kecepatan
+    pub(crate) fn bounce(&mut self) {
+        self.direction *= -1.0;
+        self.velocity *= self.restitution;
+        self.verlet = None;
     }
 
     pub fn is_play(&self) -> bool {
         self.play
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// perubahan energi per massa setelah bola memantul
+    /// sempurna selama beberapa detik
+    fn energy_drift(integrator: Integrator) -> f64 {
+        let mut state = VerticalState {
+            restitution: 1.0,
+            integrator,
+            ..Default::default()
+        };
+        let mut pos = 500.0;
+        let energy = |state: &VerticalState, pos: f32| {
+            state.gravity * pos as f64
+                + 0.5 * state.velocity * state.velocity
+        };
+
+        state.fall();
+        let initial = energy(&state, pos);
+        for _ in 0..600 {
+            state.step(
+                &mut pos,
+                10_000.0,
+                Duration::from_millis(16),
+                1.0,
+            );
+        }
+
+        (energy(&state, pos) - initial).abs() / initial
+    }
+
+    #[test]
+    fn verlet_and_rk4_drift_less_than_euler() {
+        let euler =
+            energy_drift(Integrator::SemiImplicitEuler);
+        let verlet = energy_drift(Integrator::Verlet);
+        let rk4 = energy_drift(Integrator::Rk4);
+
+        assert!(verlet < euler, "{} >= {}", verlet, euler);
+        assert!(rk4 < euler, "{} >= {}", rk4, euler);
+    }
+
+    #[test]
+    fn apex_matches_v_over_g_and_v2_over_2g() {
+        let mut state = VerticalState::default();
+        let gravity = state.gravity;
+
+        for velocity in [50.0, 400.0, 1200.0] {
+            state.throw(velocity);
+            let (time, height) = state.apex(30.0, gravity);
+
+            let expected_time = velocity / gravity;
+            assert!(
+                (time.as_secs_f64() - expected_time).abs()
+                    < 1e-9,
+                "{:?}",
+                time
+            );
+            let expected_height = 30.0
+                + velocity * velocity / (2.0 * gravity);
+            assert!(
+                (height - expected_height).abs() < 1e-9,
+                "{}",
+                height
+            );
+        }
+
+        // gravitasi sangat kecil, waktunya melebihi Duration
+        state.throw(400.0);
+        assert_eq!(
+            state.apex(30.0, 1e-300).0,
+            Duration::MAX
+        );
+
+        state.fall();
+        assert_eq!(
+            state.apex(30.0, gravity),
+            (Duration::ZERO, 30.0)
+        );
+    }
+
+    #[test]
+    fn time_to_floor_is_none_beyond_duration_max() {
+        let mut state = VerticalState::default();
+        state.fall();
+        assert!(state
+            .time_to_floor(100.0, 800.0)
+            .is_some());
+        assert_eq!(
+            state.time_to_floor(100.0, 1e-300),
+            None
+        );
+
+        state.set_state(-1e-30, 0.0);
+        assert_eq!(state.time_to_floor(100.0, 0.0), None);
+    }
+
+    #[test]
+    fn seeded_upward_velocity_rises_then_bounces() {
+        let mut state = VerticalState::new(400.0, 800.0);
+        state.integrator = Integrator::Rk4;
+        assert!(state.is_rising());
+        assert_eq!(state.velocity_up(), 400.0);
+
+        let mut pos = 10.0;
+        let mut peak = pos;
+        let mut bounce = None;
+        for _ in 0..200 {
+            bounce = state.step(
+                &mut pos,
+                10_000.0,
+                Duration::from_millis(10),
+                1.0,
+            );
+            peak = peak.max(pos);
+            if bounce.is_some() {
+                break;
+            }
+        }
+
+        // naik v²/2g = 100 dari posisi awal
+        assert!((peak - 110.0).abs() < 1.0, "{}", peak);
+        let speed = bounce.expect("bola tidak memantul");
+        assert!(speed > 400.0, "{}", speed);
+        assert!(state.is_rising());
+
+        state.set_state(-50.0, 800.0);
+        assert!(state.is_falling());
+        assert_eq!(state.velocity_up(), -50.0);
+    }
+
+    #[test]
+    fn thrown_ball_rises_then_falls() {
+        let mut state = VerticalState::default();
+        let mut pos = 10.0;
+        state.throw(400.0);
+        assert!(state.is_rising());
+        assert!(!state.is_falling());
+
+        let mut phases = vec![];
+        for _ in 0..200 {
+            let bounce = state.step(
+                &mut pos,
+                10_000.0,
+                Duration::from_millis(10),
+                1.0,
+            );
+            if bounce.is_some() {
+                break;
+            }
+            // tepat di puncak bola tidak naik maupun turun
+            let rising = state.is_rising();
+            if !rising && !state.is_falling() {
+                continue;
+            }
+            if phases.last() != Some(&rising) {
+                phases.push(rising);
+            }
+        }
+
+        assert_eq!(phases, [true, false]);
+    }
+
+    #[test]
+    fn one_bounce_at_0_8_keeps_64_percent_of_the_energy() {
+        let mut state = VerticalState {
+            restitution: 0.8,
+            integrator: Integrator::Rk4,
+            ..Default::default()
+        };
+        let height = 500.0;
+        let mut pos = height as f32;
+        state.fall();
+        assert!(
+            (state.energy_fraction(pos as f64, height)
+                - 1.0)
+                .abs()
+                < 1e-9
+        );
+
+        for _ in 0..2000 {
+            let bounce = state.step(
+                &mut pos,
+                10_000.0,
+                Duration::from_millis(1),
+                1.0,
+            );
+            if bounce.is_some() {
+                break;
+            }
+        }
+
+        // energi sebanding v², v dikali 0.8
+        let fraction =
+            state.energy_fraction(pos as f64, height);
+        assert!(
+            (fraction - 0.64).abs() < 0.01,
+            "{}",
+            fraction
+        );
+    }
+
+    #[test]
+    fn big_step_from_high_up_still_bounces() {
+        let mut state = VerticalState::default();
+        let mut pos = 20_000.0;
+        state.fall();
+
+        // ~7 detik jatuh dalam satu langkah
+        let bounce = state.step(
+            &mut pos,
+            50_000.0,
+            Duration::from_secs(8),
+            1.0,
+        );
+
+        assert!(bounce.is_some());
+        assert!(pos >= 0.0, "{}", pos);
+        assert!(state.is_play());
+    }
+
+    #[test]
+    fn half_restitution_drop_stops_in_bounded_steps() {
+        let mut state = VerticalState {
+            restitution: 0.5,
+            ..Default::default()
+        };
+        let height = 500.0;
+        let mut pos = height as f32;
+        state.fall();
+
+        // total waktu pantulan Zeno terbatas:
+        // t0 * (1 + 2e / (1 - e)) = 3 * t0
+        let t0 = (2.0 * height / state.gravity).sqrt();
+        let dt = Duration::from_millis(16);
+        let bound = (4.0 * t0 / dt.as_secs_f64()) as usize;
+        let mut bounces = 0;
+        let mut steps = 0;
+        while state.is_play() {
+            assert!(steps < bound, "{} bounces", bounces);
+            if state
+                .step(&mut pos, 10_000.0, dt, 1.0)
+                .is_some()
+            {
+                bounces += 1;
+            }
+            steps += 1;
+        }
+
+        assert!(bounces > 3, "{}", bounces);
+        assert_eq!(pos, 0.0);
+        assert!(!state.is_play());
+    }
+}
@@ -1,15 +1,372 @@
-use egui::{pos2, Response, Sense};
+use std::{sync::Arc, time::Duration};
 
-use crate::{mid_point, GLBBState};
+use egui::{
+    epaint::Galley, pos2, Response, Sense, Shape,
+    TextureHandle,
+};
+
+use crate::{
+    calculate_distance, envelope_height, CoordinateOrigin,
+    GLBBState, LaunchKind,
+};
+
+/// jumlah segmen minimum untuk sisi bola
+pub const MIN_RIM_SEGMENTS: u32 = 12;
+/// jumlah segmen maksimum untuk sisi bola
+pub const MAX_RIM_SEGMENTS: u32 = 720;
+
+/// jumlah segmen sisi bola sesuai kelilingnya, kira-kira
+/// satu segmen per pixel
+pub fn rim_segments(radius: f32) -> u32 {
+    let circumference =
+        std::f32::consts::TAU * radius.max(0.0);
+    (circumference.ceil() as u32)
+        .clamp(MIN_RIM_SEGMENTS, MAX_RIM_SEGMENTS)
+}
+
+/// kecepatan yang dianggap paling cepat oleh
+/// [`speed_gradient`]
+pub const GRADIENT_MAX_SPEED: f64 = 1000.0;
+
+/// gradasi warna dari biru saat diam sampai merah saat
+/// mencapai [`GRADIENT_MAX_SPEED`]
+pub fn speed_gradient(speed: f64) -> egui::Color32 {
+    let t =
+        (speed.abs() / GRADIENT_MAX_SPEED).clamp(0.0, 1.0);
+    let slow = egui::Color32::BLUE;
+    let fast = egui::Color32::RED;
+    let channel = |slow: u8, fast: u8| {
+        egui::lerp(slow as f64..=fast as f64, t).round()
+            as u8
+    };
+
+    egui::Color32::from_rgb(
+        channel(slow.r(), fast.r()),
+        channel(slow.g(), fast.g()),
+        channel(slow.b(), fast.b()),
+    )
+}
+
+/// ringkasan shape hasil gambar yang stabil untuk
+/// dibandingkan antar versi, bukan perbandingan pixel
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapeSummary {
+    /// jumlah shape, isi [`Shape::Vec`] ikut dihitung
+    pub count: usize,
+    /// gabungan kotak semua shape yang terlihat
+    pub bounds: egui::Rect,
+    /// warna isi atau garis yang dipakai, urut sesuai
+    /// kemunculan pertama
+    pub colors: Vec<egui::Color32>,
+}
+
+impl ShapeSummary {
+    pub fn of(shapes: &[Shape]) -> Self {
+        let mut summary = ShapeSummary {
+            count: 0,
+            bounds: egui::Rect::NOTHING,
+            colors: Vec::new(),
+        };
+        for shape in shapes {
+            summary.add(shape);
+        }
+        summary
+    }
+
+    fn add(&mut self, shape: &Shape) {
+        if let Shape::Vec(shapes) = shape {
+            for shape in shapes {
+                self.add(shape);
+            }
+            return;
+        }
+
+        self.count += 1;
+        self.bounds =
+            self.bounds.union(shape.visual_bounding_rect());
+
+        // shape tanpa isi memakai warna garisnya
+        let fill_or_stroke =
+            |fill: egui::Color32, stroke: egui::Stroke| {
+                if fill == egui::Color32::TRANSPARENT {
+                    stroke.color
+                } else {
+                    fill
+                }
+            };
+        let color = match shape {
+            Shape::Circle(it) => {
+                Some(fill_or_stroke(it.fill, it.stroke))
+            }
+            Shape::LineSegment { stroke, .. } => {
+                Some(stroke.color)
+            }
+            Shape::Path(it) => {
+                Some(fill_or_stroke(it.fill, it.stroke))
+            }
+            Shape::Rect(it) => {
+                Some(fill_or_stroke(it.fill, it.stroke))
+            }
+            Shape::Mesh(it) => {
+                it.vertices.first().map(|it| it.color)
+            }
+            _ => None,
+        };
+        if let Some(color) = color {
+            if !self.colors.contains(&color) {
+                self.colors.push(color);
+            }
+        }
+    }
+}
 
 pub struct GLBBWidget<'a> {
     state: &'a mut GLBBState,
     id: Option<egui::Id>,
+    texture: Option<TextureHandle>,
+    show_fps: bool,
+    show_readout: bool,
+    show_radius: bool,
+    show_ball_label: bool,
+    ticker_interval: Option<Duration>,
+    on_bounce: Option<Box<dyn FnMut(f64) + 'a>>,
+    reference_accelerations: Vec<f64>,
+    caption: Option<(String, egui::Align2)>,
+    speed_colormap: Option<fn(f64) -> egui::Color32>,
+    debug_bounds: bool,
+    interactive: bool,
+    ghosts: Option<(usize, u32)>,
+    trail_length: usize,
+    trail_color: Option<egui::Color32>,
+    trail_width: f32,
+    envelope_speed: Option<f64>,
+    frame: bool,
+    frame_stroke: egui::Stroke,
+    shadow: bool,
 }
 
 impl<'a> GLBBWidget<'a> {
     pub fn new(state: &'a mut GLBBState) -> Self {
-        Self { state, id: None }
+        Self {
+            state,
+            id: None,
+            texture: None,
+            show_fps: false,
+            show_readout: false,
+            show_radius: false,
+            show_ball_label: false,
+            ticker_interval: None,
+            on_bounce: None,
+            reference_accelerations: Vec::new(),
+            caption: None,
+            speed_colormap: None,
+            debug_bounds: false,
+            interactive: true,
+            ghosts: None,
+            trail_length: 0,
+            trail_color: None,
+            trail_width: 2.0,
+            envelope_speed: None,
+            shadow: false,
+            frame: true,
+            frame_stroke: egui::Stroke::new(
+                1.0,
+                egui::Color32::WHITE,
+            ),
+        }
+    }
+
+    /// tampilkan waktu frame dan fps di pojok kanan atas
+    pub fn show_fps(mut self, show_fps: bool) -> Self {
+        self.show_fps = show_fps;
+        self
+    }
+
+    /// tampilkan kecepatan, percepatan dan waktu simulasi di
+    /// pojok kiri atas
+    pub fn show_readout(
+        mut self,
+        show_readout: bool,
+    ) -> Self {
+        self.show_readout = show_readout;
+        self
+    }
+
+    /// gambar garis tengah bola dengan tulisan radiusnya
+    /// dalam pixel
+    pub fn show_radius(
+        mut self,
+        show_radius: bool,
+    ) -> Self {
+        self.show_radius = show_radius;
+        self
+    }
+
+    /// tulis posisi dan kecepatan tepat di atas bola,
+    /// mengikuti bola saat bergerak
+    pub fn show_ball_label(
+        mut self,
+        show_ball_label: bool,
+    ) -> Self {
+        self.show_ball_label = show_ball_label;
+        self
+    }
+
+    /// rekam dan gambar posisi bola setiap `interval` waktu
+    /// simulasi, seperti pita ketik (ticker tape)
+    pub fn ticker_interval(
+        mut self,
+        interval: Option<Duration>,
+    ) -> Self {
+        self.ticker_interval = interval;
+        self
+    }
+
+    /// dipanggil dengan kecepatan tumbukan setiap bola
+    /// memantul di lantai, misal untuk memutar suara
+    pub fn on_bounce(
+        mut self,
+        on_bounce: impl FnMut(f64) + 'a,
+    ) -> Self {
+        self.on_bounce = Some(Box::new(on_bounce));
+        self
+    }
+
+    /// gambar lintasan analitik dari peluncuran terakhir untuk
+    /// setiap perlambatan horizontal di `accelerations`,
+    /// sebagai perbandingan
+    pub fn reference_accelerations(
+        mut self,
+        accelerations: Vec<f64>,
+    ) -> Self {
+        self.reference_accelerations = accelerations;
+        self
+    }
+
+    /// tulisan tetap di dalam area, ditempel ke sisi atau
+    /// pojok sesuai `align`
+    pub fn caption(
+        mut self,
+        text: impl Into<String>,
+        align: egui::Align2,
+    ) -> Self {
+        self.caption = Some((text.into(), align));
+        self
+    }
+
+    /// warnai sisi bola sesuai kecepatannya, misal dengan
+    /// [`speed_gradient`]. tanpa ini bola berwarna emas
+    pub fn speed_colormap(
+        mut self,
+        colormap: fn(f64) -> egui::Color32,
+    ) -> Self {
+        self.speed_colormap = Some(colormap);
+        self
+    }
+
+    /// warna sisi bola sekarang
+    pub fn ball_color(&self) -> egui::Color32 {
+        match self.speed_colormap {
+            Some(colormap) => {
+                colormap(self.state.snapshot().speed())
+            }
+            None => egui::Color32::GOLD,
+        }
+    }
+
+    /// gambar daerah yang dipakai fisika untuk membatasi
+    /// titik tengah bola, mengecil saat bola membesar
+    pub fn debug_bounds(
+        mut self,
+        debug_bounds: bool,
+    ) -> Self {
+        self.debug_bounds = debug_bounds;
+        self
+    }
+
+    /// jika `false` widget hanya menampilkan animasi, bola
+    /// tidak bisa dipindah atau dijatuhkan dengan mouse
+    pub fn interactive(
+        mut self,
+        interactive: bool,
+    ) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// gambar `count` bola transparan di posisi lama, diambil
+    /// setiap `stride_frames` frame, seperti foto strobo
+    pub fn ghosts(
+        mut self,
+        count: usize,
+        stride_frames: u32,
+    ) -> Self {
+        self.ghosts = Some((count, stride_frames));
+        self
+    }
+
+    /// gambar jejak bola sepanjang `length` frame terakhir,
+    /// 0 berarti tanpa jejak
+    pub fn trail(mut self, length: usize) -> Self {
+        self.trail_length = length;
+        self
+    }
+
+    /// warna jejak di ujung terbaru, memudar sampai
+    /// transparan di ujung lama. default warna bola yang
+    /// setengah transparan
+    pub fn trail_color(
+        mut self,
+        color: egui::Color32,
+    ) -> Self {
+        self.trail_color = Some(color);
+        self
+    }
+
+    /// tebal garis jejak
+    pub fn trail_width(mut self, width: f32) -> Self {
+        self.trail_width = width;
+        self
+    }
+
+    /// gambar batas daerah yang bisa dicapai lemparan dari
+    /// titik lempar terakhir dengan kecepatan `speed` dan
+    /// sudut berapapun. tidak digambar tanpa gravitasi
+    pub fn show_envelope(mut self, speed: f64) -> Self {
+        self.envelope_speed = Some(speed);
+        self
+    }
+
+    /// gambar garis tepi area bermain, matikan jika widget
+    /// sudah berada di dalam panel berbingkai
+    pub fn frame(mut self, frame: bool) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    /// tebal dan warna garis tepi, lihat [`Self::frame`]
+    pub fn frame_stroke(
+        mut self,
+        stroke: egui::Stroke,
+    ) -> Self {
+        self.frame_stroke = stroke;
+        self
+    }
+
+    /// gambar bayangan bola di lantai, mengecil dan memudar
+    /// saat bola naik
+    pub fn shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// gambar bola memakai texture, bukan roda
+    pub fn texture(
+        mut self,
+        texture: TextureHandle,
+    ) -> Self {
+        self.texture = Some(texture);
+        self
     }
 
     pub fn id_source(mut self, id: impl std::hash::Hash) {
@@ -17,55 +374,706 @@ impl<'a> GLBBWidget<'a> {
     }
 
     pub fn show(mut self, ui: &mut egui::Ui) -> Response {
-        let Self { state, .. } = &mut self;
+        let Self {
+            state,
+            ticker_interval,
+            on_bounce,
+            interactive,
+            ..
+        } = &mut self;
 
+        let sense = if *interactive {
+            Sense::click_and_drag()
+        } else {
+            Sense::hover()
+        };
         let response = ui.allocate_response(
             ui.available_size_before_wrap(),
-            Sense::click_and_drag(),
+            sense,
         );
-        state.size = response.rect.size();
+        state.set_bounds(response.rect.size());
+        state.ticker_interval = *ticker_interval;
+
+        if state.try_pos_max().is_none() {
+            ui.painter_at(response.rect).text(
+                response.rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "area terlalu kecil untuk bola",
+                egui::FontId::proportional(12.0),
+                egui::Color32::YELLOW,
+            );
+            return response;
+        }
+
+        let dt = state.since_last_step();
+        if let Some(speed) = state.step(dt) {
+            if let Some(on_bounce) = on_bounce {
+                on_bounce(speed);
+            }
+        }
+
+        if self.trail_length > 0 {
+            self.state.record_trail(self.trail_length);
+        }
+
+        if let Some((count, stride)) = self.ghosts {
+            self.state.record_ghost(count, stride);
+        }
+
+        if self.interactive && !self.state.is_play() {
+            self.handle_drag(ui, &response);
+        }
 
-        let painter = ui.painter_at(response.rect);
+        if self.needs_repaint() {
+            ui.ctx().request_repaint();
+        }
 
-        painter.rect(
-            // egui::Rect::from_min_size(pos2(0.0, 0.0), response.rect.size()),
+        self.paint_to(
+            &ui.painter_at(response.rect),
             response.rect,
-            egui::Rounding::none(),
-            egui::Color32::TRANSPARENT,
-            egui::Stroke::new(1.0, egui::Color32::WHITE),
         );
 
-        state.clamp();
-        let max = state.pos_max();
+        if self.show_fps {
+            self.paint_fps(ui, &response);
+        }
 
-        state.horizontal.mv(&mut state.pos.x, 0.0..=max.x);
-        state.vertical.mv(&mut state.pos.y, max.y);
+        if let Some(shape) =
+            self.caption_shape(ui.ctx(), response.rect)
+        {
+            ui.painter_at(response.rect).add(shape);
+        }
 
-        if state.is_play() {
-            ui.ctx().request_repaint();
+        if self.show_radius {
+            ui.painter_at(response.rect).extend(
+                self.radius_shapes(ui.ctx(), response.rect),
+            );
         }
-        state.clamp();
 
-        self.draw_circle(ui, response.rect);
+        if self.show_ball_label {
+            ui.painter_at(response.rect).add(
+                self.ball_label_shape(
+                    ui.ctx(),
+                    response.rect,
+                ),
+            );
+        }
+
+        if self.show_readout {
+            let painter = ui.painter_at(response.rect);
+            painter.extend(
+                self.readout_shapes(
+                    ui.ctx(),
+                    response.rect,
+                ),
+            );
+        }
 
         response
     }
 
-    fn draw_circle(
+    /// tulisan fps di pojok kanan atas. galley disimpan di
+    /// memory egui dan hanya disusun ulang saat fps berubah
+    fn paint_fps(
+        &self,
+        ui: &egui::Ui,
+        response: &Response,
+    ) {
+        let dt = self.state.frame_dt().as_secs_f64();
+        let fps = if dt > 0.0 {
+            dt.recip().round() as u32
+        } else {
+            0
+        };
+
+        let id = response.id.with("fps-label");
+        let cached = ui
+            .memory()
+            .data
+            .get_temp::<(u32, Arc<Galley>)>(id)
+            .filter(|it| it.0 == fps);
+        let galley = match cached {
+            Some((_, galley)) => galley,
+            None => {
+                let frame_time = if fps > 0 {
+                    1000.0 / fps as f64
+                } else {
+                    0.0
+                };
+                let galley = ui.fonts().layout_no_wrap(
+                    format!(
+                        "{:6.1} ms {:4} fps",
+                        frame_time, fps
+                    ),
+                    egui::FontId::monospace(12.0),
+                    egui::Color32::GRAY,
+                );
+                ui.memory()
+                    .data
+                    .insert_temp(id, (fps, galley.clone()));
+                galley
+            }
+        };
+
+        let rect = response.rect;
+        let pos = egui::Align2::RIGHT_TOP
+            .anchor_rect(egui::Rect::from_min_size(
+                rect.right_top() + egui::vec2(-4.0, 4.0),
+                galley.size(),
+            ))
+            .min;
+        ui.painter_at(rect).galley(pos, galley);
+    }
+
+    /// pindahkan bola ke posisi pointer, bola dijatuhkan saat
+    /// drag dengan tombol kanan dilepas
+    fn handle_drag(
         &mut self,
-        ui: &mut egui::Ui,
+        ui: &egui::Ui,
+        response: &Response,
+    ) {
+        let Self { state, .. } = self;
+
+        let dragged_by_secondary = response
+            .dragged_by(egui::PointerButton::Secondary);
+        let drag_released = response.drag_released();
+
+        {
+            let mut memory = ui.memory();
+            let is_dragged = memory
+                .data
+                .get_temp_mut_or_default::<bool>(
+                    response
+                        .id
+                        .with("dragged-with-right-button"),
+                );
+
+            if *is_dragged && drag_released {
+                state.fall();
+            }
+
+            *is_dragged = dragged_by_secondary;
+        }
+
+        if let Some(pos) = response.interact_pointer_pos() {
+            state.pos =
+                state.pos_from_screen(response.rect, pos);
+            state.mark_dirty();
+        }
+    }
+
+    /// apakah frame berikutnya perlu diminta. saat bola diam
+    /// tidak ada repaint yang diminta sehingga egui hanya
+    /// menggambar ulang ketika ada input.
+    ///
+    /// selama bergerak repaint diminta setiap frame, jadi di
+    /// monitor 144 Hz bola digambar 144 kali per detik.
+    /// batas fps (`max_fps`) belum ada karena egui 0.18 belum
+    /// punya `request_repaint_after`, dan menunda frame
+    /// dengan sleep akan menahan seluruh UI
+    pub fn needs_repaint(&self) -> bool {
+        !self.state.is_at_rest()
+    }
+
+    /// shape dari [`Self::caption`], dibungkus supaya tidak
+    /// melebihi lebar area
+    pub fn caption_shape(
+        &self,
+        ctx: &egui::Context,
+        rect: egui::Rect,
+    ) -> Option<Shape> {
+        let (text, align) = self.caption.as_ref()?;
+        let rect = rect.shrink(4.0);
+
+        let galley = ctx.fonts().layout(
+            text.clone(),
+            egui::FontId::proportional(14.0),
+            egui::Color32::from_white_alpha(96),
+            rect.width().max(0.0),
+        );
+        let pos = align
+            .anchor_rect(egui::Rect::from_min_size(
+                align.pos_in_rect(&rect),
+                galley.size(),
+            ))
+            .min;
+
+        Some(Shape::galley(pos, galley))
+    }
+
+    /// panel berisi besaran dari [`GLBBState::snapshot`]
+    pub fn readout_shapes(
+        &self,
+        ctx: &egui::Context,
+        rect: egui::Rect,
+    ) -> Vec<Shape> {
+        let snapshot = self.state.snapshot();
+        let text = format!(
+            "speed {:9.2}\nvx    {:9.2}\nvy    {:9.2}\n\
+             accel {:9.2}\ntime  {:9.2}",
+            snapshot.speed(),
+            snapshot.vx,
+            snapshot.vy,
+            snapshot.accel(),
+            snapshot.sim_time.as_secs_f64(),
+        );
+
+        let galley = ctx.fonts().layout_no_wrap(
+            text,
+            egui::FontId::monospace(12.0),
+            egui::Color32::GRAY,
+        );
+
+        let padding = egui::vec2(4.0, 4.0);
+        let panel = egui::Rect::from_min_size(
+            rect.left_top() + padding,
+            galley.size() + 2.0 * padding,
+        );
+        let text_pos = panel.min + padding;
+
+        vec![
+            Shape::rect_filled(
+                panel,
+                egui::Rounding::same(2.0),
+                egui::Color32::from_black_alpha(160),
+            ),
+            Shape::galley(text_pos, galley),
+        ]
+    }
+
+    /// tulisan `(x, y)` dan kecepatan di atas bola, digeser
+    /// supaya tetap di dalam `rect`
+    pub fn ball_label_shape(
+        &self,
+        ctx: &egui::Context,
+        rect: egui::Rect,
+    ) -> Shape {
+        let Self { state, .. } = self;
+        let galley = ctx.fonts().layout_no_wrap(
+            format!(
+                "({:.0}, {:.0})\n{:.1}",
+                state.pos.x,
+                state.pos.y,
+                state.snapshot().speed(),
+            ),
+            egui::FontId::monospace(10.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+
+        let center = state.pos_to_screen(rect);
+        let size = galley.size();
+        let min = center
+            - egui::vec2(
+                size.x / 2.0,
+                state.radius() + size.y + 2.0,
+            );
+        let max = (rect.max - size).max(rect.min);
+        let pos = min.clamp(rect.min, max);
+
+        Shape::galley(pos, galley)
+    }
+
+    /// garis sepanjang diameter bola dengan tulisan
+    /// `r = N` di atasnya
+    pub fn radius_shapes(
+        &self,
+        ctx: &egui::Context,
+        rect: egui::Rect,
+    ) -> Vec<Shape> {
+        let center = self.state.pos_to_screen(rect);
+        let radius = self.state.radius();
+        let stroke =
+            egui::Stroke::new(1.0, egui::Color32::WHITE);
+
+        let galley = ctx.fonts().layout_no_wrap(
+            format!("r = {:.1}", radius),
+            egui::FontId::monospace(12.0),
+            egui::Color32::WHITE,
+        );
+        let text_pos = center
+            - egui::vec2(
+                galley.size().x / 2.0,
+                galley.size().y + 2.0,
+            );
+
+        vec![
+            Shape::line_segment(
+                [
+                    center - egui::vec2(radius, 0.0),
+                    center + egui::vec2(radius, 0.0),
+                ],
+                stroke,
+            ),
+            Shape::circle_filled(center, 2.0, stroke.color),
+            Shape::galley(text_pos, galley),
+        ]
+    }
+
+    /// gambar state sekarang ke painter tanpa menggerakkan
+    /// bola
+    pub fn paint_to(
+        &self,
+        painter: &egui::Painter,
+        rect: egui::Rect,
+    ) {
+        painter.extend(self.shapes(rect));
+    }
+
+    /// ringkasan dari [`Self::shapes`]
+    pub fn summary(
+        &self,
+        rect: egui::Rect,
+    ) -> ShapeSummary {
+        ShapeSummary::of(&self.shapes(rect))
+    }
+
+    /// semua shape dari state sekarang di dalam rect, tidak
+    /// butuh egui context sehingga bisa dipakai untuk
+    /// snapshot
+    pub fn shapes(&self, rect: egui::Rect) -> Vec<Shape> {
+        let mut shapes = Vec::new();
+
+        if self.frame {
+            shapes.push(Shape::rect_stroke(
+                rect,
+                egui::Rounding::none(),
+                self.frame_stroke,
+            ));
+        }
+
+        if self.debug_bounds {
+            shapes.push(Shape::rect_stroke(
+                self.state.bounds_screen_rect(rect),
+                egui::Rounding::none(),
+                egui::Stroke::new(
+                    1.0,
+                    egui::Color32::DEBUG_COLOR,
+                ),
+            ));
+        }
+
+        for obstacle in &self.state.obstacles {
+            shapes.push(Shape::rect_filled(
+                self.state
+                    .world_rect_to_screen(*obstacle, rect),
+                egui::Rounding::none(),
+                egui::Color32::DARK_GRAY,
+            ));
+        }
+
+        self.draw_reference_curves(&mut shapes, rect);
+        self.draw_envelope(&mut shapes, rect);
+        self.draw_ticker(&mut shapes, rect);
+        self.draw_trail(&mut shapes, rect);
+        self.draw_ghosts(&mut shapes, rect);
+        if self.shadow {
+            shapes.push(self.shadow_shape(rect));
+        }
+        self.draw_circle(
+            &mut shapes,
+            rect,
+            self.state.pos,
+            1.0,
+        );
+
+        shapes
+    }
+
+    fn draw_envelope(
+        &self,
+        shapes: &mut Vec<Shape>,
         rect: egui::Rect,
     ) {
+        const SAMPLES: usize = 64;
+
         let Self { state, .. } = self;
-        let center_pos = state.pos_to_screen(rect);
+        let speed = match self.envelope_speed {
+            Some(it) if it > 0.0 => it,
+            _ => return,
+        };
+        let origin = state
+            .last_launch()
+            .filter(|it| it.kind == LaunchKind::Projectile)
+            .map_or(state.pos, |it| it.pos);
+        // tanpa gravitasi tidak ada parabola pembatas
+        let gravity =
+            state.vertical.gravity * state.gravity_scale();
+        if gravity <= 0.0 {
+            return;
+        }
+
+        // jarak horizontal sampai batas menyentuh lantai
+        let range = speed * speed / gravity
+            * (1.0
+                + 2.0 * gravity * origin.y.max(0.0) as f64
+                    / (speed * speed))
+                .sqrt();
+
+        let points = (0..=SAMPLES)
+            .map(|it| {
+                let x = range
+                    * (2.0 * it as f64 / SAMPLES as f64
+                        - 1.0);
+                let y = envelope_height(speed, gravity, x);
+                egui::pos2(
+                    origin.x + x as f32,
+                    (origin.y + y as f32).max(0.0),
+                )
+            })
+            .map(|it| state.point_to_screen(it, rect))
+            .collect();
 
-        let painter = ui.painter_at(rect);
+        shapes.push(Shape::line(
+            points,
+            egui::Stroke::new(
+                1.0,
+                egui::Color32::LIGHT_RED
+                    .linear_multiply(0.6),
+            ),
+        ));
+    }
+
+    fn draw_reference_curves(
+        &self,
+        shapes: &mut Vec<Shape>,
+        rect: egui::Rect,
+    ) {
+        const SAMPLES: usize = 64;
+        const MAX_REFERENCE_TIME: f64 = 10.0;
+
+        let Self { state, .. } = self;
+        let count = self.reference_accelerations.len();
+
+        let (pos, velocity, direction) =
+            match state.last_launch() {
+                Some(launch) => (
+                    launch.pos,
+                    launch.velocity,
+                    match launch.kind {
+                        LaunchKind::Left => -1.0,
+                        _ => 1.0,
+                    },
+                ),
+                None => (
+                    state.pos,
+                    state.horizontal.launch_velocity(),
+                    1.0,
+                ),
+            };
+        let gravity =
+            state.vertical.gravity * state.gravity_scale();
+        // waktu sampai bola dari ketinggian awal menyentuh
+        // lantai, tanpa gravitasi bola tidak pernah jatuh
+        let fall_time = if gravity > 0.0 {
+            (2.0 * pos.y.max(0.0) as f64 / gravity).sqrt()
+        } else {
+            0.0
+        };
+
+        for (i, &acceleration) in
+            self.reference_accelerations.iter().enumerate()
+        {
+            let stop_time = if acceleration > 0.0 {
+                velocity / acceleration
+            } else {
+                f64::INFINITY
+            };
+
+            // bola di lantai hanya bergerak horizontal
+            // sampai berhenti
+            let duration = if fall_time > 0.0 {
+                fall_time
+            } else {
+                stop_time.min(MAX_REFERENCE_TIME)
+            };
+
+            let points = (0..=SAMPLES)
+                .map(|it| {
+                    let t = duration * it as f64
+                        / SAMPLES as f64;
+                    let x = calculate_distance(
+                        velocity,
+                        -acceleration,
+                        t.min(stop_time),
+                    );
+                    let y = calculate_distance(
+                        0.0, -gravity, t,
+                    );
+                    egui::pos2(
+                        pos.x + (direction * x) as f32,
+                        (pos.y + y as f32).max(0.0),
+                    )
+                })
+                .map(|it| state.point_to_screen(it, rect))
+                .collect();
+
+            let color = egui::color::Hsva::new(
+                i as f32 / count as f32,
+                0.7,
+                0.9,
+                0.5,
+            );
+            shapes.push(Shape::line(
+                points,
+                egui::Stroke::new(1.0, color),
+            ));
+        }
+    }
+
+    /// garis jejak bola, ruas paling lama paling transparan
+    fn draw_trail(
+        &self,
+        shapes: &mut Vec<Shape>,
+        rect: egui::Rect,
+    ) {
+        if self.trail_length == 0 {
+            return;
+        }
+
+        let color = self.trail_color.unwrap_or_else(|| {
+            self.ball_color().linear_multiply(0.5)
+        });
+        let points = self
+            .state
+            .trail()
+            .iter()
+            .map(|it| self.state.point_to_screen(*it, rect))
+            .collect::<Vec<_>>();
+        let count = points.len();
+
+        for (i, segment) in points.windows(2).enumerate() {
+            let alpha = (i + 1) as f32 / count as f32;
+            shapes.push(Shape::line_segment(
+                [segment[0], segment[1]],
+                egui::Stroke::new(
+                    self.trail_width,
+                    color.linear_multiply(alpha),
+                ),
+            ));
+        }
+    }
+
+    /// elips gelap di lantai tepat di bawah bola
+    pub fn shadow_shape(&self, rect: egui::Rect) -> Shape {
+        const SEGMENTS: usize = 32;
+
+        let Self { state, .. } = self;
+        let floor_radius = state.radius_at(0.0);
+        let floor_center = state
+            .point_to_screen(pos2(state.pos.x, 0.0), rect);
+        let center = match state.origin {
+            CoordinateOrigin::BottomLeft => {
+                floor_center + egui::vec2(0.0, floor_radius)
+            }
+            CoordinateOrigin::TopLeft => {
+                floor_center - egui::vec2(0.0, floor_radius)
+            }
+        };
+
+        // 0 di lantai, 1 di langit-langit
+        let height = if state.size.y > 0.0 {
+            (state.pos.y / state.size.y).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let scale = 1.0 - 0.5 * height;
+        let radius =
+            egui::vec2(floor_radius, floor_radius * 0.25)
+                * scale;
+
+        let points = (0..SEGMENTS)
+            .map(|it| {
+                let angle = std::f32::consts::TAU
+                    * it as f32
+                    / SEGMENTS as f32;
+                center
+                    + egui::vec2(
+                        angle.cos() * radius.x,
+                        angle.sin() * radius.y,
+                    )
+            })
+            .collect();
+
+        Shape::convex_polygon(
+            points,
+            egui::Color32::from_black_alpha(
+                (120.0 * (1.0 - height)) as u8,
+            ),
+            egui::Stroke::none(),
+        )
+    }
+
+    /// bola di posisi lama, makin lama makin transparan
+    fn draw_ghosts(
+        &self,
+        shapes: &mut Vec<Shape>,
+        rect: egui::Rect,
+    ) {
+        let ghosts = self.state.ghosts();
+        let count = ghosts.len();
+        for (i, pos) in ghosts.iter().enumerate() {
+            // yang paling lama di depan
+            let alpha = (i + 1) as f32 / (count + 1) as f32;
+            self.draw_circle(
+                shapes,
+                rect,
+                *pos,
+                alpha * 0.5,
+            );
+        }
+    }
+
+    fn draw_ticker(
+        &self,
+        shapes: &mut Vec<Shape>,
+        rect: egui::Rect,
+    ) {
+        let Self { state, .. } = self;
+        if state.ticker_interval.is_none() {
+            return;
+        }
+
+        for pos in state.ticker_marks() {
+            shapes.push(Shape::circle_filled(
+                state.point_to_screen(*pos, rect),
+                2.0,
+                egui::Color32::LIGHT_BLUE,
+            ));
+        }
+    }
+
+    /// gambar bola di posisi `pos` dengan transparansi
+    /// `alpha`, 1.0 berarti tidak transparan
+    fn draw_circle(
+        &self,
+        shapes: &mut Vec<Shape>,
+        rect: egui::Rect,
+        pos: egui::Pos2,
+        alpha: f32,
+    ) {
+        let Self { state, .. } = self;
+        let center_pos = state.point_to_screen(pos, rect);
+        let radius = state.radius_at(pos.y);
+
+        if let Some(texture) = &self.texture {
+            shapes.push(Shape::image(
+                texture.id(),
+                egui::Rect::from_center_size(
+                    center_pos,
+                    egui::vec2(radius, radius) * 2.0,
+                ),
+                egui::Rect::from_min_max(
+                    pos2(0.0, 0.0),
+                    pos2(1.0, 1.0),
+                ),
+                egui::Color32::WHITE.linear_multiply(alpha),
+            ));
+            return;
+        }
 
         let create_wheel_point =
             |radius: f32, pos: egui::Pos2, wheel: u32| {
                 let wheel_f = wheel as f32;
-                (0..(wheel as u32))
-                    .into_iter()
+                (0..wheel)
                     .map(|it| it as f32)
                     // ngebagi 360 bagian menjadi wheel bagian
                     .map(|it| it * 360.0 / wheel_f)
@@ -83,31 +1091,31 @@ impl<'a> GLBBWidget<'a> {
                     .collect::<Vec<_>>()
             };
 
-        let color = egui::Color32::GOLD;
-
-        let stroke =
-            egui::Stroke::new(1.0, egui::Color32::RED);
+        let color =
+            self.ball_color().linear_multiply(alpha);
 
-        let points = create_wheel_point(
-            state.radius(),
-            center_pos,
-            8,
+        let stroke = egui::Stroke::new(
+            1.0,
+            egui::Color32::RED.linear_multiply(alpha),
         );
-        for i in 0..points.len() {
-            painter.add(egui::Shape::line_segment(
-                [center_pos, points[i]],
+
+        let points =
+            create_wheel_point(radius, center_pos, 8);
+        for point in points {
+            shapes.push(Shape::line_segment(
+                [center_pos, point],
                 stroke,
             ));
         }
 
         let stroke = egui::Stroke::new(1.0, color);
         let points = create_wheel_point(
-            state.radius(),
+            radius,
             center_pos,
-            360,
+            rim_segments(radius),
         );
         for i in 0..points.len() {
-            painter.add(egui::Shape::line_segment(
+            shapes.push(Shape::line_segment(
                 [points[i], points[(i + 1) % points.len()]],
                 stroke,
             ));
@@ -139,11 +1147,7 @@ impl<'a> GLBBWidget<'a> {
         //             texture
         //         }
         //     };
-        // let pos = center_pos - state.radius_size();
-        // let rect = egui::Rect::from_min_size(
-        //     pos,
-        //     state.radius_size() * egui::vec2(2.0, 2.0),
-        // );
+        // let rect = state.screen_rect(rect);
         // egui::Image::new(
         //     image_texture.id(),
         //     state.radius_size(),
@@ -151,3 +1155,647 @@ impl<'a> GLBBWidget<'a> {
         // .paint_at(ui, rect);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// area widget di layar
+    const RECT: egui::Rect = egui::Rect {
+        min: egui::pos2(10.0, 20.0),
+        max: egui::pos2(410.0, 320.0),
+    };
+
+    /// state seukuran [`RECT`] dengan bola di `pos`
+    fn state_at(pos: egui::Pos2) -> GLBBState {
+        let mut state = GLBBState::default();
+        state.set_bounds(RECT.size());
+        state.pos = pos;
+        state
+    }
+
+    /// jalankan satu frame egui tanpa jendela
+    fn run_frame(
+        ctx: &egui::Context,
+        add: impl FnOnce(&mut egui::Ui),
+    ) -> egui::FullOutput {
+        run_frame_with(ctx, vec![], add)
+    }
+
+    /// jalankan satu frame egui tanpa jendela dengan input
+    /// `events`
+    fn run_frame_with(
+        ctx: &egui::Context,
+        events: Vec<egui::Event>,
+        add: impl FnOnce(&mut egui::Ui),
+    ) -> egui::FullOutput {
+        let input = egui::RawInput {
+            screen_rect: Some(RECT),
+            events,
+            ..Default::default()
+        };
+        ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, add);
+        })
+    }
+
+    /// semua teks yang digambar dalam satu frame
+    fn painted_text(
+        ctx: &egui::Context,
+        add: impl FnOnce(&mut egui::Ui),
+    ) -> Vec<String> {
+        run_frame(ctx, add)
+            .shapes
+            .into_iter()
+            .filter_map(|clipped| match clipped.1 {
+                Shape::Text(text) => {
+                    Some(text.galley.text().to_owned())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn texture_is_painted_over_the_ball() {
+        let ctx = egui::Context::default();
+        let texture = ctx.load_texture(
+            "ball",
+            egui::ColorImage::new(
+                [4, 4],
+                egui::Color32::WHITE,
+            ),
+        );
+        let mut state = state_at(pos2(120.0, 80.0));
+        let screen_rect = state.screen_rect(RECT);
+
+        let shapes = GLBBWidget::new(&mut state)
+            .frame(false)
+            .texture(texture)
+            .shapes(RECT);
+
+        match shapes.as_slice() {
+            [Shape::Mesh(mesh)] => {
+                assert_eq!(mesh.calc_bounds(), screen_rect)
+            }
+            shapes => {
+                panic!("bukan satu mesh: {:?}", shapes)
+            }
+        }
+    }
+
+    #[test]
+    fn shapes_stay_inside_the_rect() {
+        let mut state = state_at(pos2(0.0, 0.0));
+        let shapes =
+            GLBBWidget::new(&mut state).shapes(RECT);
+
+        assert!(!shapes.is_empty());
+        // garis bingkai setebal 1 pixel berada tepat di tepi
+        let rect = RECT.expand(0.5);
+        for shape in &shapes {
+            let bounds = shape.visual_bounding_rect();
+            assert!(
+                rect.contains_rect(bounds),
+                "{:?}",
+                bounds
+            );
+        }
+    }
+
+    #[test]
+    fn rim_segments_grow_with_radius_and_stay_bounded() {
+        let radii = [0.0, 1.0, 5.0, 30.0, 60.0, 200.0, 1e6];
+        let segments = radii.map(rim_segments);
+
+        assert!(segments
+            .windows(2)
+            .all(|it| it[0] <= it[1]));
+        assert!(segments[3] < segments[4]);
+        assert_eq!(segments[0], MIN_RIM_SEGMENTS);
+        assert_eq!(segments[6], MAX_RIM_SEGMENTS);
+    }
+
+    #[test]
+    fn readout_is_painted_only_when_enabled() {
+        let ctx = egui::Context::default();
+        let mut state = state_at(pos2(120.0, 80.0));
+
+        let hidden = painted_text(&ctx, |ui| {
+            GLBBWidget::new(&mut state).show(ui);
+        });
+        let shown = painted_text(&ctx, |ui| {
+            GLBBWidget::new(&mut state)
+                .show_readout(true)
+                .show(ui);
+        });
+
+        let is_readout = |text: &String| {
+            ["speed", "vx", "vy", "accel", "time"]
+                .iter()
+                .all(|label| text.contains(label))
+        };
+        assert!(!hidden.iter().any(is_readout));
+        assert!(
+            shown.iter().any(is_readout),
+            "{:?}",
+            shown
+        );
+    }
+
+    #[test]
+    fn on_bounce_reports_the_first_floor_contact() {
+        let ctx = egui::Context::default();
+        let mut state = state_at(pos2(120.0, 50.0));
+        state.fall();
+
+        let mut speeds = Vec::new();
+        for _ in 0..200 {
+            state.set_frame_clock(
+                crate::Now::with_elapsed(
+                    Duration::from_millis(5),
+                ),
+            );
+            run_frame(&ctx, |ui| {
+                GLBBWidget::new(&mut state)
+                    .on_bounce(|speed| speeds.push(speed))
+                    .show(ui);
+            });
+            if !speeds.is_empty() {
+                break;
+            }
+        }
+
+        match speeds.as_slice() {
+            [speed] => assert!(*speed > 0.0, "{}", speed),
+            speeds => panic!("{:?}", speeds),
+        }
+    }
+
+    #[test]
+    fn each_reference_acceleration_draws_a_polyline() {
+        let polylines = |shapes: Vec<Shape>| {
+            shapes
+                .iter()
+                .filter(|it| {
+                    matches!(it, Shape::Path(path) if !path.closed)
+                })
+                .count()
+        };
+        let mut state = state_at(pos2(20.0, 200.0));
+
+        let base = polylines(
+            GLBBWidget::new(&mut state).shapes(RECT),
+        );
+        for accelerations in [
+            vec![5.0],
+            vec![5.0, 10.0, 20.0],
+            vec![0.0, 1.0],
+        ] {
+            let count = accelerations.len();
+            let shapes = GLBBWidget::new(&mut state)
+                .reference_accelerations(accelerations)
+                .shapes(RECT);
+            assert_eq!(polylines(shapes), base + count);
+        }
+    }
+
+    #[test]
+    fn caption_sits_at_its_anchor() {
+        let ctx = egui::Context::default();
+        // font baru tersedia setelah frame pertama
+        run_frame(&ctx, |_| {});
+        let mut state = state_at(pos2(120.0, 80.0));
+        let inner = RECT.shrink(4.0);
+
+        for align in [
+            egui::Align2::LEFT_TOP,
+            egui::Align2::CENTER_CENTER,
+            egui::Align2::RIGHT_BOTTOM,
+        ] {
+            let shape = GLBBWidget::new(&mut state)
+                .caption("glbb", align)
+                .caption_shape(&ctx, RECT);
+            let text = match shape {
+                Some(Shape::Text(text)) => text,
+                shape => panic!("{:?}", shape),
+            };
+
+            let rect = egui::Rect::from_min_size(
+                text.pos,
+                text.galley.size(),
+            );
+            let offset = align.pos_in_rect(&rect)
+                - align.pos_in_rect(&inner);
+            assert!(offset.length() < 1e-3, "{:?}", align);
+            assert!(RECT.contains_rect(rect));
+        }
+
+        assert!(GLBBWidget::new(&mut state)
+            .caption_shape(&ctx, RECT)
+            .is_none());
+    }
+
+    #[test]
+    fn speed_gradient_spans_slow_to_fast() {
+        let mut state = state_at(pos2(120.0, 80.0));
+        assert_eq!(
+            GLBBWidget::new(&mut state).ball_color(),
+            egui::Color32::GOLD
+        );
+
+        let color = |state: &mut GLBBState| {
+            GLBBWidget::new(state)
+                .speed_colormap(speed_gradient)
+                .ball_color()
+        };
+        assert_eq!(color(&mut state), egui::Color32::BLUE);
+
+        state.apply_impulse(egui::vec2(
+            4.0 * GRADIENT_MAX_SPEED as f32,
+            0.0,
+        ));
+        assert_eq!(color(&mut state), egui::Color32::RED);
+    }
+
+    #[test]
+    fn no_repaint_is_requested_at_rest() {
+        let ctx = egui::Context::default();
+        let mut state = state_at(pos2(120.0, 80.0));
+        let repaint = |state: &mut GLBBState| {
+            // egui bisa meminta repaint di frame awal untuk
+            // menata ulang, lihat frame terakhir saja
+            (0..3)
+                .map(|_| {
+                    run_frame(&ctx, |ui| {
+                        GLBBWidget::new(state).show(ui);
+                    })
+                    .needs_repaint
+                })
+                .last()
+                .unwrap()
+        };
+
+        assert!(!repaint(&mut state));
+
+        state.apply_impulse(egui::vec2(300.0, 0.0));
+        assert!(repaint(&mut state));
+
+        state.reset();
+        assert!(!repaint(&mut state));
+    }
+
+    #[test]
+    fn debug_bounds_draws_the_clamp_region() {
+        let debug_rects = |shapes: Vec<Shape>| -> Vec<_> {
+            shapes
+                .into_iter()
+                .filter_map(|it| {
+                    match it {
+                    Shape::Rect(rect)
+                        if rect.stroke.color
+                            == egui::Color32::DEBUG_COLOR =>
+                    {
+                        Some(rect.rect)
+                    }
+                    _ => None,
+                }
+                })
+                .collect()
+        };
+        let mut state = state_at(pos2(120.0, 80.0));
+        let bounds = state.bounds_screen_rect(RECT);
+
+        assert!(debug_rects(
+            GLBBWidget::new(&mut state).shapes(RECT)
+        )
+        .is_empty());
+        assert_eq!(
+            debug_rects(
+                GLBBWidget::new(&mut state)
+                    .debug_bounds(true)
+                    .shapes(RECT)
+            ),
+            [bounds]
+        );
+        assert!(RECT.contains_rect(bounds));
+    }
+
+    #[test]
+    fn frame_false_paints_no_border() {
+        let borders = |shapes: Vec<Shape>| -> Vec<_> {
+            shapes
+                .into_iter()
+                .filter_map(|it| match it {
+                    Shape::Rect(rect)
+                        if rect.rect == RECT =>
+                    {
+                        Some(rect.stroke)
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut state = state_at(pos2(120.0, 80.0));
+        let stroke =
+            egui::Stroke::new(3.0, egui::Color32::RED);
+
+        assert_eq!(
+            borders(
+                GLBBWidget::new(&mut state)
+                    .frame_stroke(stroke)
+                    .shapes(RECT)
+            ),
+            [stroke]
+        );
+        assert!(borders(
+            GLBBWidget::new(&mut state)
+                .frame(false)
+                .shapes(RECT)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn shadow_sits_on_the_floor_and_shrinks_with_height() {
+        let shadow_rect = |state: &mut GLBBState| {
+            let widget =
+                GLBBWidget::new(state).shadow(true);
+            let shadow = widget.shadow_shape(RECT);
+            assert!(widget.shapes(RECT).contains(&shadow));
+            shadow.visual_bounding_rect()
+        };
+        let mut state = state_at(pos2(120.0, 0.0));
+        // tanpa perspektif x layar tidak bergantung pada y
+        state.perspective(false);
+        assert!(!GLBBWidget::new(&mut state)
+            .shapes(RECT)
+            .iter()
+            .any(|it| matches!(it, Shape::Path(_))));
+
+        let low = shadow_rect(&mut state);
+        state.pos.y = 250.0;
+        let high = shadow_rect(&mut state);
+
+        for shadow in [low, high] {
+            // elips menempel pada garis lantai
+            assert!(
+                shadow.y_range().contains(&RECT.bottom()),
+                "{:?}",
+                shadow
+            );
+            assert!(
+                (shadow.center().x
+                    - state
+                        .point_to_screen(state.pos, RECT)
+                        .x)
+                    .abs()
+                    < 1.0
+            );
+        }
+        assert!(high.width() < low.width());
+    }
+
+    /// bandingkan ringkasan gambar dengan snapshot yang
+    /// disimpan, kotak boleh beda sedikit karena pembulatan
+    fn assert_summary(
+        actual: ShapeSummary,
+        expected: ShapeSummary,
+    ) {
+        let close = |a: egui::Pos2, b: egui::Pos2| {
+            (a - b).length() < 0.01
+        };
+        assert!(
+            actual.count == expected.count
+                && actual.colors == expected.colors
+                && close(
+                    actual.bounds.min,
+                    expected.bounds.min
+                )
+                && close(
+                    actual.bounds.max,
+                    expected.bounds.max
+                ),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn default_ball_matches_the_stored_summary() {
+        let mut state = state_at(pos2(120.0, 80.0));
+
+        // 8 jari-jari merah dan 164 segmen sisi emas,
+        // ditambah garis bingkai putih
+        assert_summary(
+            GLBBWidget::new(&mut state).summary(RECT),
+            ShapeSummary {
+                count: 173,
+                bounds: RECT.expand(0.5),
+                colors: vec![
+                    egui::Color32::WHITE,
+                    egui::Color32::RED,
+                    egui::Color32::GOLD,
+                ],
+            },
+        );
+        assert_summary(
+            GLBBWidget::new(&mut state)
+                .frame(false)
+                .summary(RECT),
+            ShapeSummary {
+                count: 172,
+                bounds: egui::Rect::from_min_max(
+                    pos2(129.5, 185.5),
+                    pos2(182.5, 238.5),
+                ),
+                colors: vec![
+                    egui::Color32::RED,
+                    egui::Color32::GOLD,
+                ],
+            },
+        );
+    }
+
+    #[test]
+    fn non_interactive_widget_ignores_a_drag() {
+        let dragged = |interactive: bool| {
+            let ctx = egui::Context::default();
+            let mut state = state_at(pos2(20.0, 20.0));
+            let button =
+                |pos, pressed| egui::Event::PointerButton {
+                    pos,
+                    button: egui::PointerButton::Primary,
+                    pressed,
+                    modifiers: Default::default(),
+                };
+            let from = RECT.center();
+            let to = from + egui::vec2(60.0, 40.0);
+
+            for events in [
+                vec![egui::Event::PointerMoved(from)],
+                vec![button(from, true)],
+                vec![egui::Event::PointerMoved(to)],
+                vec![button(to, false)],
+            ] {
+                run_frame_with(&ctx, events, |ui| {
+                    GLBBWidget::new(&mut state)
+                        .interactive(interactive)
+                        .show(ui);
+                });
+            }
+            state.pos
+        };
+
+        assert_ne!(dragged(true), pos2(20.0, 20.0));
+        assert_eq!(dragged(false), pos2(20.0, 20.0));
+    }
+
+    #[test]
+    fn trail_fades_from_newest_to_oldest() {
+        let mut state = state_at(pos2(20.0, 150.0));
+        state.apply_impulse(egui::vec2(300.0, 0.0));
+        for _ in 0..8 {
+            state.step(Duration::from_millis(16));
+            state.record_trail(8);
+        }
+
+        let shapes = GLBBWidget::new(&mut state)
+            .trail(8)
+            .trail_color(egui::Color32::RED)
+            .trail_width(3.0)
+            .shapes(RECT);
+        let strokes: Vec<_> = shapes
+            .iter()
+            .filter_map(|it| match it {
+                // bola juga digambar dengan garis
+                Shape::LineSegment { stroke, .. }
+                    if stroke.width == 3.0 =>
+                {
+                    Some(*stroke)
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(strokes.len(), 7);
+        let alpha =
+            |stroke: &egui::Stroke| stroke.color.a();
+        assert!(strokes
+            .windows(2)
+            .all(|it| { alpha(&it[0]) < alpha(&it[1]) }));
+    }
+
+    #[test]
+    fn show_radius_draws_the_diameter_and_its_label() {
+        let ctx = egui::Context::default();
+        let mut state = state_at(pos2(120.0, 80.0));
+        let is_label =
+            |text: &String| text.starts_with("r = ");
+
+        let hidden = painted_text(&ctx, |ui| {
+            GLBBWidget::new(&mut state).show(ui);
+        });
+        assert!(!hidden.iter().any(is_label));
+        let shown = painted_text(&ctx, |ui| {
+            GLBBWidget::new(&mut state)
+                .show_radius(true)
+                .show(ui);
+        });
+        assert!(shown.iter().any(is_label), "{:?}", shown);
+
+        let label = format!("r = {:.1}", state.radius());
+        let center = state.pos_to_screen(RECT);
+        let radius = state.radius();
+        let shapes = GLBBWidget::new(&mut state)
+            .radius_shapes(&ctx, RECT);
+        match shapes.as_slice() {
+            [Shape::LineSegment { points, .. }, _, Shape::Text(text)] =>
+            {
+                assert_eq!(
+                    *points,
+                    [
+                        center - egui::vec2(radius, 0.0),
+                        center + egui::vec2(radius, 0.0),
+                    ]
+                );
+                assert_eq!(text.galley.text(), label);
+            }
+            shapes => panic!("{:?}", shapes),
+        }
+    }
+
+    #[test]
+    fn ball_label_sits_just_above_the_ball() {
+        let ctx = egui::Context::default();
+        // font baru tersedia setelah satu frame
+        run_frame(&ctx, |_| {});
+        let label_rect =
+            |state: &mut GLBBState| match GLBBWidget::new(
+                state,
+            )
+            .ball_label_shape(&ctx, RECT)
+            {
+                Shape::Text(text) => {
+                    text.visual_bounding_rect()
+                }
+                shape => panic!("{:?}", shape),
+            };
+
+        let mut state = state_at(pos2(120.0, 80.0));
+        let label = label_rect(&mut state);
+        let center = state.pos_to_screen(RECT);
+        let top = center.y - state.radius();
+        assert!(
+            (label.center().x - center.x).abs() < 1.0,
+            "{:?} {:?}",
+            label,
+            center
+        );
+        assert!(label.bottom() <= top, "{:?}", label);
+        assert!(label.bottom() > top - 5.0, "{:?}", label);
+
+        // di pojok kiri atas tulisan tetap di dalam rect
+        state.pos = pos2(0.0, state.pos_max().y);
+        let label = label_rect(&mut state);
+        assert!(RECT.contains_rect(label), "{:?}", label);
+    }
+
+    #[test]
+    fn envelope_uses_the_effective_gravity() {
+        let envelope_top = |state: &mut GLBBState| {
+            let color = egui::Color32::LIGHT_RED
+                .linear_multiply(0.6);
+            GLBBWidget::new(state)
+                .show_envelope(300.0)
+                .shapes(RECT)
+                .into_iter()
+                .find_map(|it| match it {
+                    Shape::Path(path)
+                        if path.stroke.color == color =>
+                    {
+                        Some(
+                            path.visual_bounding_rect()
+                                .top(),
+                        )
+                    }
+                    _ => None,
+                })
+        };
+        let mut state = state_at(pos2(200.0, 50.0));
+        let normal = envelope_top(&mut state).unwrap();
+
+        // gravitasi dua kali lipat, puncaknya setengah tinggi
+        state.zones.push(crate::GravityZone {
+            y: 0.0..=1000.0,
+            gravity_multiplier: 2.0,
+        });
+        let heavy = envelope_top(&mut state).unwrap();
+        assert!(heavy > normal, "{} <= {}", heavy, normal);
+
+        state.zones.clear();
+        state.billiard_mode(true);
+        assert_eq!(envelope_top(&mut state), None);
+    }
+}